@@ -1,8 +1,6 @@
-use std::{
-    cell::RefCell,
-    hash::{DefaultHasher, Hash, Hasher},
-    rc::Rc,
-};
+use std::{cell::RefCell, hash::Hash, rc::Rc};
+
+use dsbuild::util::hash::Hasher128;
 
 use crate::{addr, req, rsm};
 
@@ -70,11 +68,11 @@ impl dsbuild::Process for Raft {
     }
 
     fn hash(&self) -> dsbuild::HashType {
-        let mut h = DefaultHasher::new();
+        let mut h = Hasher128::new();
         match &*self.state.borrow() {
             Some(state) => state.hash(&mut h),
             None => 0.hash(&mut h),
         }
-        h.finish()
+        h.finish128()
     }
 }
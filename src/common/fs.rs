@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::model;
@@ -8,7 +9,7 @@ use super::mode::is_real;
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Represents file system error.
-#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FsError {
     /// Requested file not found.
     #[error("file {file:?} not found")]
@@ -36,6 +37,10 @@ pub enum FsError {
     #[error("file not available")]
     FileNotAvailable,
 
+    /// Input/output operation failed.
+    #[error("input/output failure")]
+    IoFailure,
+
     /// Failed to resolve path.
     #[error("bad path")]
     BadPath {
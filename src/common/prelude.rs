@@ -2,7 +2,8 @@
 
 pub use super::fs::{File, FsError, FsResult};
 pub use super::process::{
-    log, send_local, set_random_timer, set_timer, sleep, spawn, Address, Process,
+    choose, log, random, random_range, send_local, set_random_timer, set_timer, sleep, spawn,
+    Address, Process,
 };
 pub use super::rpc::{rpc, RpcError, RpcListener, RpcRequest, RpcResponse, RpcResult};
 pub use super::rt::JoinHandle;
@@ -2,6 +2,15 @@
 
 use std::{any::Any, fmt::Display, future::Future, time::Duration};
 
+use rand::{
+    distr::{
+        uniform::{SampleRange, SampleUniform},
+        Distribution, StandardUniform,
+    },
+    rngs::SmallRng,
+    Rng, SeedableRng,
+};
+
 use crate::{
     model::{self, timer},
     real, HashType, Timer,
@@ -139,6 +148,55 @@ pub fn set_random_timer(min_duration: Duration, max_duration: Duration) -> Timer
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Allows to make nondeterministic choice of one of `n` options,
+/// returns index of the chosen option.
+///
+/// In the MC [`crate::mc`], every option is explored as separate branch.
+/// In the simulation [`crate::detsim`], option is chosen using the seeded random generator,
+/// and in the real environment [`crate::real`] using the random generator of the node.
+pub fn choose(n: usize) -> usize {
+    if is_sim() {
+        model::context::Context::current().choose(n)
+    } else {
+        real::context::Context::current().choose(n)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Allows to get random value of the provided type.
+///
+/// In the MC [`crate::mc`], values are fixed, so [`choose`] must be used
+/// to explore the different values.
+/// In the simulation [`crate::detsim`], values are generated using the seeded random generator,
+/// and in the real environment [`crate::real`] using the random generator of the node.
+pub fn random<T>() -> T
+where
+    StandardUniform: Distribution<T>,
+{
+    random_source().random()
+}
+
+/// Allows to get random value from the provided range (see [`random`]).
+pub fn random_range<T, R>(range: R) -> T
+where
+    T: SampleUniform,
+    R: SampleRange<T>,
+{
+    random_source().random_range(range)
+}
+
+fn random_source() -> SmallRng {
+    let seed = if is_sim() {
+        model::context::Context::current().random_seed()
+    } else {
+        real::context::Context::current().random_seed()
+    };
+    SmallRng::seed_from_u64(seed)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// Allows to sleep on the provided time.
 pub async fn sleep(duration: Duration) {
     set_timer(duration).await;
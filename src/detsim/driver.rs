@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, VecDeque},
     time::Duration,
 };
 
@@ -12,10 +12,10 @@ use crate::{
         outcome::{EventOutcome, EventOutcomeKind},
         Event,
     },
-    Address,
+    Address, HashType,
 };
 
-use super::StepConfig;
+use super::{trace::StepRecord, StepConfig};
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -26,6 +26,10 @@ pub struct Driver {
     last_tcp: BTreeMap<(usize, bool), Duration>,
     last_rpc: BTreeMap<(Address, Address), Duration>,
     time: Duration,
+    history: Option<Vec<StepRecord>>,
+    forced: VecDeque<StepRecord>,
+    forced_choices: VecDeque<usize>,
+    diverged: Option<String>,
 }
 
 impl EventDriver for Driver {
@@ -78,9 +82,20 @@ impl EventDriver for Driver {
         assert!(exist);
     }
 
-    fn hash_pending(&self) -> u64 {
+    fn hash_pending(&self) -> HashType {
         0
     }
+
+    fn choose(&mut self, n: usize) -> usize {
+        if let Some(forced) = self.forced_choices.front().copied().filter(|v| *v >= n) {
+            self.diverge(format!("can not choose {} of {}", forced, n));
+        }
+        self.resolve(|rng| rng.random_range(0..n))
+    }
+
+    fn random_seed(&mut self, _proc: &Address) -> u64 {
+        self.resolve(|rng| rng.random::<u64>() as usize) as u64
+    }
 }
 
 impl Driver {
@@ -92,16 +107,84 @@ impl Driver {
             queue: Default::default(),
             rng: SmallRng::seed_from_u64(seed),
             time: Duration::ZERO,
+            history: None,
+            forced: Default::default(),
+            forced_choices: Default::default(),
+            diverged: None,
         }
     }
 
+    /// Resolve the nondeterministic value made by the process using the random generator,
+    /// or take it from the forced step, and record it into the last step.
+    fn resolve(&mut self, f: impl FnOnce(&mut SmallRng) -> usize) -> usize {
+        let value = self
+            .forced_choices
+            .pop_front()
+            .unwrap_or_else(|| f(&mut self.rng));
+        if let Some(step) = self.history.as_mut().and_then(|h| h.last_mut()) {
+            step.choices.push(value);
+        }
+        value
+    }
+
+    /// Starts recording outcomes of the next steps.
+    pub fn record_history(&mut self) {
+        self.history.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns the recorded steps or `None` if the recording is not enabled.
+    pub fn history(&self) -> Option<&[StepRecord]> {
+        self.history.as_deref()
+    }
+
+    pub fn force_steps(&mut self, steps: VecDeque<StepRecord>) {
+        self.forced = steps;
+        self.diverged = None;
+    }
+
+    pub fn diverged(&self) -> Option<&str> {
+        self.diverged.as_deref()
+    }
+
+    /// Stops following the forced steps, because they can not be made.
+    fn diverge(&mut self, reason: String) {
+        self.forced.clear();
+        self.forced_choices.clear();
+        self.diverged = Some(reason);
+    }
+
+    /// Returns the next event and its forced udp drop decision.
+    /// Event is taken from the forced steps if there are any,
+    /// and then the choices made after the step are forced too.
+    /// If the forced step can not be made, the simulation diverges from them.
+    fn next_event(&mut self) -> Option<(Duration, usize, Option<bool>)> {
+        self.forced_choices.clear();
+        if let Some(step) = self.forced.pop_front() {
+            match self.info.get(&step.event_id) {
+                None => self.diverge(format!("event {} is not pending", step.event_id)),
+                Some(_) if step.time < self.time => {
+                    self.diverge(format!("time of event {} goes back", step.event_id))
+                }
+                Some((t, _)) => {
+                    let removed = self.queue.remove(&(*t, step.event_id));
+                    assert!(removed);
+                    self.forced_choices = step.choices.into();
+                    return Some((step.time, step.event_id, Some(step.drop)));
+                }
+            }
+        }
+        let (time, event_id) = self.queue.pop_first()?;
+        Some((time, event_id, None))
+    }
+
     pub fn next_event_outcome(&mut self, cfg: &StepConfig) -> Option<EventOutcome> {
-        if let Some((time, event_id)) = self.queue.pop_first() {
-            let (t, event_info) = self.info.remove(&event_id).unwrap();
-            assert_eq!(t, time);
+        if let Some((time, event_id, forced_drop)) = self.next_event() {
+            let (_, event_info) = self.info.remove(&event_id).unwrap();
             let kind = match &event_info {
                 EventInfo::UdpMessage(_) => {
-                    let dropped = self.rng.random_range(0.0..1.0) < cfg.udp_packet_drop_prob;
+                    let dropped = forced_drop.unwrap_or_else(|| {
+                        self.rng.random_range(0.0..1.0) < cfg.udp_packet_drop_prob
+                    });
                     if dropped {
                         EventOutcomeKind::UdpMessageDropped()
                     } else {
@@ -119,12 +202,21 @@ impl Driver {
                 EventInfo::RpcMessage(_) => EventOutcomeKind::RpcMessageDelivered,
                 EventInfo::RpcEvent(e) => EventOutcomeKind::RpcEventHappen(e.kind.rpc_result()),
             };
+            let dropped = matches!(kind, EventOutcomeKind::UdpMessageDropped());
+            if let Some(history) = self.history.as_mut() {
+                history.push(StepRecord {
+                    event_id,
+                    time,
+                    drop: dropped,
+                    choices: Vec::new(),
+                });
+            }
             let outcome = EventOutcome {
                 event_id,
                 kind,
                 time,
             };
-            self.time = t;
+            self.time = time;
             Some(outcome)
         } else {
             None
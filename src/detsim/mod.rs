@@ -2,8 +2,10 @@
 
 mod driver;
 mod sim;
+mod trace;
 
 pub use sim::{Simulation, StepConfig};
+pub use crate::util::replay::ReplayError;
 
 ////////////////////////////////////////////////////////////////////////////////
 
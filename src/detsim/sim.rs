@@ -1,8 +1,11 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, path::Path, rc::Rc};
 
-use crate::model::{event::driver::EventDriver, system::System, SystemHandle};
+use crate::{
+    model::{event::driver::EventDriver, system::System, SystemHandle},
+    util::replay::ReplayError,
+};
 
-use super::driver::Driver;
+use super::{driver::Driver, trace};
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -86,4 +89,48 @@ impl Simulation {
     pub fn system(&self) -> SystemHandle {
         self.system.handle()
     }
+
+    /// Start recording outcomes of the simulation steps,
+    /// so they can be saved with [Simulation::save_trace].
+    /// Recording is disabled by default, because the outcomes of all steps are kept in memory.
+    /// Must be called before the first step for the saved trace to be replayed.
+    pub fn enable_trace(&self) {
+        self.driver.borrow_mut().record_history();
+    }
+
+    /// Save outcomes of the simulation steps recorded so far
+    /// into the file in the JSON format.
+    /// The saved steps can be replayed with [Simulation::replay].
+    /// Returns error if the recording is not enabled (see [Simulation::enable_trace]).
+    pub fn save_trace(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        match self.driver.borrow().history() {
+            Some(steps) => trace::save(steps, path),
+            None => Err(std::io::Error::other("trace recording is not enabled")),
+        }
+    }
+
+    /// Make the next simulation steps follow the outcomes
+    /// saved by [Simulation::save_trace].
+    ///
+    /// The system must be built and driven by the same user actions
+    /// as the one which produced the trace.
+    /// After the saved steps are exhausted, the simulation continues as usual.
+    /// Returns error if the trace can not be loaded or can not be made by any simulation.
+    ///
+    /// If the simulation diverges from the trace,
+    /// the remaining saved steps are not followed (see [Simulation::replay_diverged]).
+    pub fn replay(&self, trace_file: impl AsRef<Path>) -> Result<(), ReplayError> {
+        let steps = trace::load(trace_file)?;
+        self.driver.borrow_mut().force_steps(steps);
+        Ok(())
+    }
+
+    /// Returns error if the simulation steps diverged from the replayed trace,
+    /// for example because the system is built in the other way.
+    pub fn replay_diverged(&self) -> Result<(), ReplayError> {
+        match self.driver.borrow().diverged() {
+            Some(reason) => Err(ReplayError::Diverged(reason.to_string())),
+            None => Ok(()),
+        }
+    }
 }
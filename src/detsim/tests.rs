@@ -1,15 +1,12 @@
 use std::time::Duration;
 
-use crate::{
-    detsim::Simulation, detsim::StepConfig,
-    model::node::Node,
-};
+use crate::{detsim::ReplayError, detsim::Simulation, detsim::StepConfig, model::node::Node};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    model::net::send_message, send_local, sleep, spawn,
-    Address, HashType, Process,
+    choose, model::net::send_message, random, random_range, send_local, sleep, spawn, Address,
+    HashType, Process,
 };
 
 use crate::model::fs::file::File;
@@ -92,6 +89,53 @@ impl Process for Sleeper {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Default)]
+pub struct Chooser {}
+
+impl Process for Chooser {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, _content: String) {
+        spawn(async move {
+            sleep(Duration::from_millis(10)).await;
+            send_local(choose(1000).to_string());
+        });
+    }
+
+    fn hash(&self) -> HashType {
+        unreachable!()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Default)]
+pub struct Randomizer {}
+
+impl Process for Randomizer {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, _content: String) {
+        spawn(async move {
+            sleep(Duration::from_millis(10)).await;
+            let value = random::<u64>();
+            let ranged = random_range(10..20);
+            assert!((10..20).contains(&ranged));
+            send_local(format!("{} {}", value, ranged));
+        });
+    }
+
+    fn hash(&self) -> HashType {
+        unreachable!()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Serialize, Deserialize)]
 pub enum Msg {
     CreateFile(String),
@@ -247,3 +291,225 @@ fn basic_fs() {
     assert_eq!(locals.len(), 1);
     assert_eq!(locals[0], "hello");
 }
+
+////////////////////////////////////////////////////////////////////////////////
+
+fn build_ping_sim(seed: u64) -> Simulation {
+    let sim = Simulation::new(seed);
+    let mut node = Node::new("n1");
+    node.add_proc(
+        "pinger",
+        Pinger {
+            receiver: "n2:ponger".into(),
+        },
+    )
+    .unwrap();
+    sim.system().add_node(node).unwrap();
+    let mut node = Node::new("n2");
+    node.add_proc("ponger", Ponger {}).unwrap();
+    sim.system().add_node(node).unwrap();
+    sim
+}
+
+#[test]
+fn save_and_replay() {
+    let cfg = StepConfig::new(0.5);
+    let path =
+        std::env::temp_dir().join(format!("dsbuild-detsim-replay-{}.json", std::process::id()));
+
+    let run = |sim: &Simulation| {
+        for i in 0..10 {
+            sim.system()
+                .send_local(&"n1:pinger".into(), i.to_string())
+                .unwrap();
+        }
+        sim.step_until_no_events(&cfg);
+        sim.system().log().to_string()
+    };
+
+    let sim = build_ping_sim(123);
+    sim.enable_trace();
+    let expected = run(&sim);
+    sim.save_trace(&path).unwrap();
+
+    // other seed gives other outcomes without replay
+    let sim = build_ping_sim(321);
+    sim.replay(&path).unwrap();
+    let replayed = run(&sim);
+    assert_eq!(expected, replayed);
+    sim.replay_diverged().unwrap();
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn diverged_replay() {
+    let cfg = StepConfig::no_drops();
+    let path = std::env::temp_dir().join(format!(
+        "dsbuild-detsim-diverged-{}.json",
+        std::process::id()
+    ));
+
+    let run = |sim: &Simulation, messages: usize| {
+        for i in 0..messages {
+            sim.system()
+                .send_local(&"n1:pinger".into(), i.to_string())
+                .unwrap();
+        }
+        sim.step_until_no_events(&cfg);
+        sim.system().read_locals("n1", "pinger").unwrap()
+    };
+
+    let sim = build_ping_sim(123);
+    sim.enable_trace();
+    run(&sim, 10);
+    sim.save_trace(&path).unwrap();
+
+    // the other system can not follow the trace,
+    // but the simulation continues without it
+    let sim = build_ping_sim(123);
+    sim.replay(&path).unwrap();
+    assert_eq!(run(&sim, 5).len(), 5);
+    assert!(matches!(
+        sim.replay_diverged(),
+        Err(ReplayError::Diverged(..))
+    ));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn diverged_choice() {
+    let path =
+        std::env::temp_dir().join(format!("dsbuild-detsim-choice-{}.json", std::process::id()));
+    let step = r#"{"event_id": 0, "time": {"secs": 0, "nanos": 10000000}, "drop": false, "choices": [5000]}"#;
+    std::fs::write(&path, format!(r#"{{"version": 1, "steps": [{}]}}"#, step)).unwrap();
+
+    let sim = Simulation::new(123);
+    let mut node = Node::new("n1");
+    node.add_proc("chooser", Chooser::default()).unwrap();
+    sim.system().add_node(node).unwrap();
+    sim.replay(&path).unwrap();
+    sim.system()
+        .send_local(&"n1:chooser".into(), "choose")
+        .unwrap();
+    sim.step_until_no_events(&StepConfig::no_drops());
+
+    // value is chosen as usual
+    let locals = sim.system().read_locals("n1", "chooser").unwrap();
+    assert!(locals[0].parse::<usize>().unwrap() < 1000);
+    assert!(matches!(
+        sim.replay_diverged(),
+        Err(ReplayError::Diverged(reason)) if reason == "can not choose 5000 of 1000"
+    ));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn trace_not_recorded_by_default() {
+    let path = std::env::temp_dir().join(format!(
+        "dsbuild-detsim-disabled-{}.json",
+        std::process::id()
+    ));
+    let sim = build_ping_sim(123);
+    sim.system().send_local(&"n1:pinger".into(), "0").unwrap();
+    sim.step_until_no_events(&StepConfig::no_drops());
+    assert!(sim.save_trace(&path).is_err());
+    assert!(!path.exists());
+}
+
+#[test]
+fn bad_trace_rejected() {
+    let path = std::env::temp_dir().join(format!("dsbuild-detsim-bad-{}.json", std::process::id()));
+    let step = r#"{"event_id": 0, "time": {"secs": 0, "nanos": 0}, "drop": false}"#;
+    let json = format!(r#"{{"version": 1, "steps": [{}, {}]}}"#, step, step);
+    std::fs::write(&path, json).unwrap();
+
+    let sim = build_ping_sim(123);
+    assert!(matches!(sim.replay(&path), Err(ReplayError::Format(..))));
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(sim.replay(&path), Err(ReplayError::Io(..))));
+}
+
+#[test]
+fn choose_and_replay() {
+    let cfg = StepConfig::no_drops();
+    let path =
+        std::env::temp_dir().join(format!("dsbuild-detsim-choose-{}.json", std::process::id()));
+
+    let run = |seed: u64, replay: bool| {
+        let sim = Simulation::new(seed);
+        let mut node = Node::new("n1");
+        node.add_proc("chooser", Chooser::default()).unwrap();
+        sim.system().add_node(node).unwrap();
+        if replay {
+            sim.replay(&path).unwrap();
+        } else {
+            sim.enable_trace();
+        }
+        for _ in 0..10 {
+            sim.system()
+                .send_local(&"n1:chooser".into(), "choose")
+                .unwrap();
+        }
+        sim.step_until_no_events(&cfg);
+        if !replay {
+            sim.save_trace(&path).unwrap();
+        }
+        sim.system().read_locals("n1", "chooser").unwrap()
+    };
+
+    // the same seed gives the same choices
+    let expected = run(123, false);
+    assert_eq!(expected.len(), 10);
+    assert_eq!(run(123, false), expected);
+
+    // other seed gives other choices without replay
+    assert_ne!(run(321, false), expected);
+    run(123, false);
+    assert_eq!(run(321, true), expected);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn random_and_replay() {
+    let cfg = StepConfig::no_drops();
+    let path =
+        std::env::temp_dir().join(format!("dsbuild-detsim-random-{}.json", std::process::id()));
+
+    let run = |seed: u64, replay: bool| {
+        let sim = Simulation::new(seed);
+        let mut node = Node::new("n1");
+        node.add_proc("randomizer", Randomizer::default()).unwrap();
+        sim.system().add_node(node).unwrap();
+        if replay {
+            sim.replay(&path).unwrap();
+        } else {
+            sim.enable_trace();
+        }
+        for _ in 0..10 {
+            sim.system()
+                .send_local(&"n1:randomizer".into(), "random")
+                .unwrap();
+        }
+        sim.step_until_no_events(&cfg);
+        if !replay {
+            sim.save_trace(&path).unwrap();
+        }
+        sim.system().read_locals("n1", "randomizer").unwrap()
+    };
+
+    // the same seed gives the same values
+    let expected = run(123, false);
+    assert_eq!(expected.len(), 10);
+    assert_eq!(run(123, false), expected);
+
+    // other seed gives other values without replay
+    assert_ne!(run(321, false), expected);
+    run(123, false);
+    assert_eq!(run(321, true), expected);
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,77 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    path::Path,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::util::replay::ReplayError;
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Version of the trace format.
+/// Must be increased on every incompatible change of the format.
+const TRACE_VERSION: u32 = 1;
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Outcome of the single simulation step.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StepRecord {
+    pub event_id: usize,
+    pub time: Duration,
+    pub drop: bool,
+    /// Nondeterministic choices and random seeds made by the processes after the step.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TraceRecord {
+    version: u32,
+    steps: Vec<StepRecord>,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn save(steps: &[StepRecord], path: impl AsRef<Path>) -> std::io::Result<()> {
+    let record = TraceRecord {
+        version: TRACE_VERSION,
+        steps: steps.to_vec(),
+    };
+    let json = serde_json::to_string_pretty(&record).expect("trace is always serializable");
+    std::fs::write(path, json)
+}
+
+/// Loads the steps of the trace and checks that they can be made by the simulation:
+/// every event is handled once and the time does not go back.
+pub fn load(path: impl AsRef<Path>) -> Result<VecDeque<StepRecord>, ReplayError> {
+    let json = std::fs::read_to_string(path).map_err(ReplayError::Io)?;
+    let record: TraceRecord =
+        serde_json::from_str(&json).map_err(|e| ReplayError::Format(e.to_string()))?;
+    if record.version != TRACE_VERSION {
+        return Err(ReplayError::Format(format!(
+            "unsupported trace version {}, expected {}",
+            record.version, TRACE_VERSION
+        )));
+    }
+    let mut handled = BTreeSet::new();
+    let mut time = Duration::ZERO;
+    for step in record.steps.iter() {
+        if !handled.insert(step.event_id) {
+            return Err(ReplayError::Format(format!(
+                "event {} is handled twice",
+                step.event_id
+            )));
+        }
+        if step.time < time {
+            return Err(ReplayError::Format(format!(
+                "time of event {} goes back",
+                step.event_id
+            )));
+        }
+        time = step.time;
+    }
+    Ok(record.steps.into())
+}
@@ -9,9 +9,9 @@
 mod common;
 
 pub use common::{
-    log, rpc, send_local, set_random_timer, set_timer, sleep, spawn, Address, File, FsError,
-    FsResult, JoinHandle, Process, RpcError, RpcListener, RpcRequest, RpcResponse, RpcResult,
-    Timer,
+    choose, log, random, random_range, rpc, send_local, set_random_timer, set_timer, sleep, spawn,
+    Address, File, FsError, FsResult, JoinHandle, Process, RpcError, RpcListener, RpcRequest,
+    RpcResponse, RpcResult, Timer,
 };
 
 pub use common::prelude;
@@ -1,18 +1,34 @@
-use std::collections::HashSet;
-
-use crate::mc::search::{
-    control::{ApplyFn, ApplyFunctor, GoalFn, InvariantFn, PruneFn},
-    error::SearchError,
-    log::SearchLog,
-    searcher::Searcher,
-    state::{SearchState, StateTrace},
-    step::StateTraceStep,
+use std::{collections::HashSet, path::Path, sync::Arc};
+
+use crate::{
+    mc::search::{
+        config::SearchConfig,
+        control::{
+            system_hash, AbstractionFn, ApplyFn, ApplyFunctor, GoalFn, InvariantFn, PruneFn,
+            TransitionInvariantFn,
+        },
+        error::{InvariantViolation, ReplayError, SearchError, SearchErrorKind},
+        log::SearchLog,
+        schedule::{follow, ScheduleConstraint},
+        searcher::Searcher,
+        state::{SearchState, StateTrace},
+        step::StateTraceStep,
+        trace::{trace_from_json, ApplyRegistry},
+        transition::Transitions,
+        visited::VisitedSet,
+    },
+    mc::StateView,
+    model::HashType,
 };
 
 use super::wrapper::ApplyFnWrapper;
 
 ////////////////////////////////////////////////////////////////////////////////
 
+type VisitedFn = Arc<dyn Fn() -> Box<dyn VisitedSet> + Send + Sync>;
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// Allows to control model checking workflow.
 ///
 /// Operates with search states, which corresponds to the states of the testing system.
@@ -25,18 +41,23 @@ use super::wrapper::ApplyFnWrapper;
 /// of the system.
 pub struct ModelChecker {
     states: Vec<StateTrace>,
+    functions: ApplyRegistry,
+    transitions: Transitions,
+    visited: VisitedFn,
 }
 
 impl ModelChecker {
     /// Make new checker with method, which initializes system model.
+    /// In the saved traces build step is referenced by the name `build`.
     pub fn new_with_build(build: impl ApplyFn) -> Self {
-        let mut start = StateTrace::new();
-        let apply_fn = ApplyFnWrapper::new(build);
-        let step = StateTraceStep::Apply(Box::new(apply_fn));
-        start.add_step(step);
-        Self {
-            states: vec![start],
-        }
+        let mut checker = Self {
+            states: vec![StateTrace::new()],
+            functions: Default::default(),
+            transitions: Default::default(),
+            visited: Arc::new(|| Box::new(HashSet::<HashType>::new())),
+        };
+        checker.apply_named("build", build);
+        checker
     }
 
     /// Run check with the specified searcher.
@@ -51,10 +72,33 @@ impl ModelChecker {
         invariant: impl InvariantFn,
         prune: impl PruneFn,
         goal: impl GoalFn,
+        searcher: impl Searcher,
+    ) -> Result<SearchLog, SearchError> {
+        self.check_with_abstraction(invariant, prune, goal, system_hash, searcher)
+    }
+
+    /// Same as [ModelChecker::check], but the visited states are deduplicated
+    /// by the key returned by the provided abstraction instead of the system model hash.
+    /// Allows to consider states, which differ only in the not relevant details,
+    /// for example counters or message ids, as the same.
+    pub fn check_with_abstraction(
+        self,
+        invariant: impl InvariantFn,
+        prune: impl PruneFn,
+        goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
         mut searcher: impl Searcher,
     ) -> Result<SearchLog, SearchError> {
-        let mut visited = HashSet::default();
-        searcher.check(self.states.clone(), &mut visited, invariant, prune, goal)
+        let mut visited = (self.visited)();
+        searcher.check(
+            self.states.clone(),
+            visited.as_mut(),
+            invariant,
+            &self.transitions,
+            prune,
+            goal,
+            abstraction,
+        )
     }
 
     /// Run search using the provided searcher and collect states,
@@ -69,26 +113,142 @@ impl ModelChecker {
         invariant: impl InvariantFn,
         prune: impl PruneFn,
         goal: impl GoalFn,
+        searcher: impl Searcher,
+    ) -> Result<SearchLog, SearchError> {
+        self.collect_with_abstraction(invariant, prune, goal, system_hash, searcher)
+    }
+
+    /// Same as [ModelChecker::collect], but the visited states are deduplicated
+    /// by the key returned by the provided abstraction instead of the system model hash,
+    /// so only one state is collected from the states with the same key.
+    pub fn collect_with_abstraction(
+        &mut self,
+        invariant: impl InvariantFn,
+        prune: impl PruneFn,
+        goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
         mut searcher: impl Searcher,
     ) -> Result<SearchLog, SearchError> {
         let mut states = Vec::default();
         std::mem::swap(&mut states, &mut self.states);
-        let mut visited = HashSet::default();
-        let collect_info = searcher.collect(states, &mut visited, invariant, prune, goal)?;
+        let mut visited = (self.visited)();
+        let collect_info = searcher.collect(
+            states,
+            visited.as_mut(),
+            invariant,
+            &self.transitions,
+            prune,
+            goal,
+            abstraction,
+        )?;
+        self.states = collect_info.states;
+        Ok(collect_info.log)
+    }
+
+    /// Follow the schedule prefix from every stored state
+    /// and store the states reached after the last constraint,
+    /// so the next [ModelChecker::check] or [ModelChecker::collect] call
+    /// explores the region of the state space after the prefix.
+    ///
+    /// Every constraint is matched against the steps available in the state
+    /// (see [`ScheduleConstraint`]), which are generated according to the provided config.
+    /// If several steps match the constraint, all of them are followed.
+    /// Checks provided invariant for each state visited along the prefix.
+    pub fn follow(
+        &mut self,
+        schedule: &[ScheduleConstraint],
+        cfg: &SearchConfig,
+        invariant: impl InvariantFn,
+    ) -> Result<SearchLog, SearchError> {
+        let collect_info = follow(
+            self.states.clone(),
+            schedule,
+            cfg,
+            invariant,
+            &self.transitions,
+        )?;
         self.states = collect_info.states;
         Ok(collect_info.log)
     }
 
+    /// Add invariant, which is checked on every transition explored
+    /// by the next [ModelChecker::check] and [ModelChecker::collect] calls
+    /// together with the invariant provided to them.
+    ///
+    /// The searchers keep the expanded state as the previous state of the transitions
+    /// to its children, which takes the snapshot (see [`SearchConfig::max_snapshots`]).
+    /// If the limit of snapshots is reached, the previous state is replayed from its trace,
+    /// so the search with transition invariants is slower.
+    /// Transitions made by the applied functions are not checked.
+    pub fn transition_invariant(&mut self, f: impl TransitionInvariantFn) {
+        self.transitions.add(f);
+    }
+
+    /// Set the function, which makes the set of the visited states
+    /// for the next [ModelChecker::check] and [ModelChecker::collect] calls.
+    ///
+    /// By default the exact set is kept in memory. The searches, which do not fit into the memory,
+    /// can use the bitstate hashing ([`crate::mc::BitstateSet`])
+    /// or the set spilled to the disk ([`crate::mc::DiskVisitedSet`]).
+    pub fn visited_set<S: VisitedSet + 'static>(
+        &mut self,
+        make: impl Fn() -> S + Send + Sync + 'static,
+    ) {
+        self.visited = Arc::new(move || Box::new(make()));
+    }
+
     /// Apply function for each current stored state.
     /// Allows to crash nodes in some states or send local messages for processes,
     /// for example.
+    ///
+    /// In the saved traces the function is referenced by the name `apply-<n>`,
+    /// where `<n>` is the number of functions applied before.
     pub fn apply(&mut self, f: impl ApplyFn) {
-        let f = Box::new(ApplyFnWrapper::new(f));
+        let name = format!("apply-{}", self.functions.len());
+        self.apply_named(name, f);
+    }
+
+    /// Same as [ModelChecker::apply], but the function is referenced
+    /// by the provided name in the saved traces (see [ModelChecker::replay]).
+    /// Names must be unique within the checker.
+    pub fn apply_named(&mut self, name: impl Into<String>, f: impl ApplyFn) {
+        let name = name.into();
+        let f: Box<dyn ApplyFunctor> = Box::new(ApplyFnWrapper::new(name.clone(), f));
+        let prev = self.functions.insert(name.clone(), f.clone());
+        assert!(prev.is_none(), "function '{}' is already applied", name);
         self.states
             .iter_mut()
             .for_each(|s| s.add_step(StateTraceStep::Apply(f.clone())));
     }
 
+    /// Replay trace saved from the search error (see [SearchError::save_trace]).
+    ///
+    /// Apply steps of the trace are resolved by names among the functions
+    /// applied to this checker, so the checker must be made in the same way
+    /// as the one which produced the trace. The provided invariant
+    /// is checked in the last state of the trace.
+    /// Returns the error reproduced by the trace (process panic or invariant violation)
+    /// or `None` if the error is not reproduced.
+    /// Returns [`ReplayError`] if the trace can not be replayed.
+    pub fn replay(
+        &self,
+        trace_file: impl AsRef<Path>,
+        invariant: impl InvariantFn,
+    ) -> Result<Option<SearchErrorKind>, ReplayError> {
+        let json = std::fs::read_to_string(trace_file).map_err(ReplayError::Io)?;
+        let trace = trace_from_json(&json, &self.functions)?;
+        let state = match SearchState::from_trace(&trace) {
+            Ok(state) => state,
+            Err(kind) => return Ok(Some(kind)),
+        };
+        let log = state.system.handle().log();
+        if let Err(report) = invariant(StateView::new(&state, trace.clone())) {
+            let err = InvariantViolation { trace, log, report };
+            return Ok(Some(SearchErrorKind::InvariantViolation(err)));
+        }
+        Ok(None)
+    }
+
     /// Apply provided fucntion without mutating stored states.
     pub fn for_each(&self, f: impl ApplyFn) {
         self.states.iter().for_each(|s| {
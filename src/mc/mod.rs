@@ -8,16 +8,29 @@ mod wrapper;
 ////////////////////////////////////////////////////////////////////////////////
 
 pub use search::{
+    best_first::BestFirstSearcher,
     bfs::BfsSearcher,
     config::{SearchConfig, SearchConfigBuilder},
+    control::AbstractionFn,
     control::ApplyFn,
     control::GoalFn,
     control::InvariantFn,
     control::PruneFn,
+    control::ScoreFn,
+    control::TransitionInvariantFn,
     dfs::DfsSearcher,
     error,
-    log::SearchLog,
+    fairness::{FairEvent, Fairness},
+    graph::{GraphRecorder, NodeMark},
+    log::{Progress, SearchLog},
+    parallel::ParallelSearcher,
+    property::{Predicate, Property},
+    random::RandomWalkSearcher,
+    schedule::ScheduleConstraint,
+    shrink::shrink,
     state::StateView,
+    visited::{BitstateSet, DiskVisitedSet, VisitedSet},
+    workload::Workload,
 };
 
 pub use checker::ModelChecker;
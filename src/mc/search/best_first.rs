@@ -0,0 +1,397 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+use super::{
+    budget::SearchBudget,
+    config::SearchConfig,
+    control::{AbstractionFn, GoalFn, InvariantFn, PruneFn, ScoreFn},
+    entry::Entry,
+    error::{AllPruned, Cycled, InvariantViolation, LivenessViolation, SearchErrorKind},
+    log::{SearchClock, SearchLog},
+    por::{SleepCache, Visit},
+    searcher::{CollectInfo, Searcher},
+    state::{Snapshots, StateTrace},
+    transition::Transitions,
+    violations::Violations,
+    visited::VisitedSet,
+};
+
+use crate::{mc::error::SearchError, mc::StateView};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Entry of the frontier ordered by the score,
+/// entries with the same score are ordered by the insertion.
+struct Scored {
+    score: i64,
+    order: Reverse<usize>,
+    entry: Entry,
+}
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.score, self.order).cmp(&(other.score, other.order))
+    }
+}
+
+/// States waiting to be visited.
+/// States are added with the score of their parent, which is known
+/// when the parent is visited, so the states are not made only to be scored.
+struct Frontier {
+    heap: BinaryHeap<Scored>,
+    added: usize,
+}
+
+impl Frontier {
+    fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            added: 0,
+        }
+    }
+
+    /// Add state with the score of its parent.
+    fn add(&mut self, score: i64, entry: Entry) {
+        self.heap.push(Scored {
+            score,
+            order: Reverse(self.added),
+            entry,
+        });
+        self.added += 1;
+    }
+
+    fn pop(&mut self) -> Option<(i64, Entry)> {
+        self.heap.pop().map(|s| (s.score, s.entry))
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Represents best-first searcher, which visits the states
+/// with the greatest score first.
+///
+/// Allows to reach the deep goals, if the score estimates the progress towards them.
+/// Every state is scored when it is visited and its children are ordered
+/// by this score, so no state is made from the trace only to be scored.
+/// The states, which differ by the choices of the processes (see [`crate::choose`]),
+/// are ordered by the score of their common parent.
+pub struct BestFirstSearcher<F: ScoreFn> {
+    cfg: SearchConfig,
+    score: F,
+}
+
+impl<F: ScoreFn> BestFirstSearcher<F> {
+    /// Make new searcher with provided config and score function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the config contains temporal properties or fairness constraints,
+    /// which are not checked by the searcher.
+    pub fn new(cfg: SearchConfig, score: F) -> Self {
+        assert!(
+            cfg.properties.is_empty(),
+            "temporal properties are not checked by best-first search"
+        );
+        assert!(
+            cfg.fairness.is_empty(),
+            "fairness constraints are not checked by best-first search"
+        );
+        Self { cfg, score }
+    }
+}
+
+impl<F: ScoreFn> Searcher for BestFirstSearcher<F> {
+    fn check(
+        &mut self,
+        start: Vec<StateTrace>,
+        visited: &mut dyn VisitedSet,
+        invariant: impl InvariantFn,
+        transitions: &Transitions,
+        prune: impl PruneFn,
+        goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
+    ) -> Result<SearchLog, SearchError> {
+        let mut log = SearchLog::new();
+        let mut sleeping = SleepCache::default();
+        let mut clock = SearchClock::new(&self.cfg);
+        let mut snapshots = Snapshots::new(&self.cfg);
+        let mut frontier = Frontier::new();
+        for v in start {
+            frontier.add(i64::MAX, Entry::start(v));
+        }
+        let mut budget = SearchBudget::new(&self.cfg);
+        let mut violations = Violations::new(&self.cfg);
+
+        let mut last_prune = None;
+        let mut last_already_meet = None;
+
+        let mut goal_achieved = false;
+        while let Some((parent_score, entry)) = frontier.pop() {
+            let Entry {
+                trace: mut v,
+                sleep,
+                prev,
+                snapshot,
+                ..
+            } = entry;
+            clock.tick(&mut log, frontier.len());
+            let state = snapshots
+                .restore(snapshot, &v, &mut log)
+                .map_err(|k| violations.stop(SearchError::new(k, &log)))?;
+            budget
+                .check(&log, &v, &state)
+                .map_err(|err| violations.stop(err))?;
+            log.visit(&v);
+
+            // branch on the other choices made by the last step
+            let choices = state.branch_choices(&mut v);
+            log.choose(&choices);
+            for u in choices {
+                let sibling = Entry {
+                    trace: u,
+                    sleep: sleep.clone(),
+                    prev: prev.clone(),
+                    ..Default::default()
+                };
+                frontier.add(parent_score, sibling);
+            }
+            let system = state.system.handle();
+
+            // make state view
+            let view = StateView::new(&state, v.clone());
+
+            let h = abstraction(view.clone());
+            let visit = sleep.visit(visited, &mut sleeping, h);
+            if visit == Visit::New {
+                log.visited_unique += 1;
+            }
+            let already_meet = visit == Visit::Explored;
+
+            // check invariant and the transition from the previous state
+            let transition = transitions.check(prev.as_deref(), &view);
+            snapshots.release(prev);
+            if let Err(report) = invariant(view.clone()).and(transition) {
+                let err = InvariantViolation {
+                    trace: v.clone(),
+                    log: system.log(),
+                    report,
+                };
+                violations.add(err, h, &log)?;
+                continue;
+            }
+
+            // check goal achieved
+            let goal_check_result = goal(view.clone());
+            if goal_check_result.is_ok() {
+                goal_achieved = true;
+                continue;
+            }
+
+            // check prune
+            if prune(view) {
+                log.pruned += 1;
+                last_prune = Some(AllPruned::new(v.clone(), system.log()));
+                continue;
+            }
+
+            // error if no transitions available
+            let steps = state.gen.borrow().steps(system.clone(), &self.cfg);
+            if steps.is_empty() {
+                let err = LivenessViolation::new(v, system.log(), goal_check_result.unwrap_err());
+                let err = SearchErrorKind::LivenessViolation(err);
+                return Err(violations.stop(SearchError::new(err, &log)));
+            }
+
+            // check already meet condition
+            if already_meet {
+                last_already_meet = Some(Cycled::new(v, system.log(), h));
+                continue;
+            }
+
+            // check depth budget
+            if budget.depth_exceeded(&v, &state) {
+                continue;
+            }
+
+            // branch, the first child is resumed from the state
+            // or the state is kept as the previous state of the children
+            let children = sleep.branch(&self.cfg, &state.gen.borrow(), steps);
+            log.branch(children.iter().map(|(s, _)| s));
+            if children.is_empty() {
+                continue;
+            }
+            let score = (self.score)(StateView::new(&state, v.clone()));
+            let (prev, mut snapshot) = transitions.keep(state, &mut snapshots);
+            for (s, sleep) in children {
+                let mut u = v.clone();
+                u.add_step(s);
+                let child = Entry {
+                    trace: u,
+                    sleep,
+                    prev: prev.clone(),
+                    snapshot: snapshot.take(),
+                    ..Default::default()
+                };
+                frontier.add(score, child);
+            }
+        }
+
+        violations.finish(&log)?;
+        budget.finish(&log)?;
+
+        if goal_achieved {
+            Ok(log)
+        } else if let Some(last_prune) = last_prune {
+            let err = SearchErrorKind::AllPruned(last_prune);
+            Err(SearchError::new(err, &log))
+        } else {
+            let err = SearchErrorKind::Cycled(last_already_meet.unwrap());
+            Err(SearchError::new(err, &log))
+        }
+    }
+
+    fn collect(
+        &mut self,
+        start: Vec<StateTrace>,
+        visited: &mut dyn VisitedSet,
+        invariant: impl InvariantFn,
+        transitions: &Transitions,
+        prune: impl PruneFn,
+        goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
+    ) -> Result<CollectInfo, SearchError> {
+        let mut log = SearchLog::new();
+        let mut sleeping = SleepCache::default();
+        let mut clock = SearchClock::new(&self.cfg);
+        let mut collected = Vec::new();
+        let mut snapshots = Snapshots::new(&self.cfg);
+        let mut frontier = Frontier::new();
+        for v in start {
+            frontier.add(i64::MAX, Entry::start(v));
+        }
+        let mut budget = SearchBudget::new(&self.cfg);
+
+        while let Some((parent_score, entry)) = frontier.pop() {
+            let Entry {
+                trace: mut v,
+                sleep,
+                prev,
+                snapshot,
+                ..
+            } = entry;
+            clock.tick(&mut log, frontier.len());
+            let state = snapshots
+                .restore(snapshot, &v, &mut log)
+                .map_err(|k| SearchError::new(k, &log))?;
+            budget.check(&log, &v, &state)?;
+            log.visit(&v);
+
+            // branch on the other choices made by the last step
+            let choices = state.branch_choices(&mut v);
+            log.choose(&choices);
+            for u in choices {
+                let sibling = Entry {
+                    trace: u,
+                    sleep: sleep.clone(),
+                    prev: prev.clone(),
+                    ..Default::default()
+                };
+                frontier.add(parent_score, sibling);
+            }
+            let system = state.system.handle();
+
+            // make state view
+            let view = StateView::new(&state, v.clone());
+
+            let h = abstraction(view.clone());
+            let visit = sleep.visit(visited, &mut sleeping, h);
+            if visit == Visit::New {
+                log.visited_unique += 1;
+            }
+            let already_meet = visit == Visit::Explored;
+
+            // check invariant and the transition from the previous state
+            let transition = transitions.check(prev.as_deref(), &view);
+            snapshots.release(prev);
+            invariant(view.clone()).and(transition).map_err(|report| {
+                let err = InvariantViolation {
+                    trace: v.clone(),
+                    log: system.log(),
+                    report,
+                };
+                let kind = SearchErrorKind::InvariantViolation(err);
+                SearchError::new(kind, &log)
+            })?;
+
+            // check prune and already meet condition
+            if prune(view.clone()) {
+                log.pruned += 1;
+                continue;
+            }
+            if already_meet {
+                continue;
+            }
+
+            // check goal achieved
+            if goal(view).is_ok() {
+                collected.push(v);
+                continue;
+            }
+
+            // check depth budget
+            if budget.depth_exceeded(&v, &state) {
+                continue;
+            }
+
+            // branch, the first child is resumed from the state
+            // or the state is kept as the previous state of the children
+            let steps = state.gen.borrow().steps(system, &self.cfg);
+            let children = sleep.branch(&self.cfg, &state.gen.borrow(), steps);
+            log.branch(children.iter().map(|(s, _)| s));
+            if children.is_empty() {
+                continue;
+            }
+            let score = (self.score)(StateView::new(&state, v.clone()));
+            let (prev, mut snapshot) = transitions.keep(state, &mut snapshots);
+            for (s, sleep) in children {
+                let mut u = v.clone();
+                u.add_step(s);
+                let child = Entry {
+                    trace: u,
+                    sleep,
+                    prev: prev.clone(),
+                    snapshot: snapshot.take(),
+                    ..Default::default()
+                };
+                frontier.add(score, child);
+            }
+        }
+
+        budget.finish(&log)?;
+
+        Ok(CollectInfo {
+            states: collected,
+            log,
+        })
+    }
+}
@@ -1,27 +1,45 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::VecDeque;
 
 use super::{
+    budget::SearchBudget,
     config::SearchConfig,
-    control::{GoalFn, InvariantFn, PruneFn},
-    error::{AllPruned, InvariantViolation, LivenessViolation, SearchErrorKind},
-    log::SearchLog,
+    control::{AbstractionFn, GoalFn, InvariantFn, PruneFn},
+    entry::Entry,
+    error::{AllPruned, InvariantViolation, LivenessViolation, PropertyViolation, SearchErrorKind},
+    fairness::FairnessGraph,
+    graph::{GraphRecorder, NodeMark},
+    log::{SearchClock, SearchLog},
+    por::{SleepCache, Visit},
     searcher::{CollectInfo, Searcher},
-    state::{SearchState, StateTrace},
+    state::{Snapshots, StateTrace},
+    transition::Transitions,
+    violations::Violations,
+    visited::VisitedSet,
 };
 
-use crate::{mc::error::Cycled, mc::error::SearchError, mc::StateView, model::system::HashType};
+use crate::{mc::error::Cycled, mc::error::SearchError, mc::StateView};
 
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Represents BFS searcher.
 pub struct BfsSearcher {
     cfg: SearchConfig,
+    graph: GraphRecorder,
 }
 
 impl BfsSearcher {
     /// Make new searcher with provided config.
     pub fn new(cfg: SearchConfig) -> Self {
-        Self { cfg }
+        Self {
+            cfg,
+            graph: GraphRecorder::disabled(),
+        }
+    }
+
+    /// Record the explored state graph into the provided recorder.
+    pub fn record_graph(mut self, recorder: GraphRecorder) -> Self {
+        self.graph = recorder;
+        self
     }
 }
 
@@ -29,52 +47,119 @@ impl Searcher for BfsSearcher {
     fn check(
         &mut self,
         start: Vec<StateTrace>,
-        visited: &mut HashSet<HashType>,
+        visited: &mut dyn VisitedSet,
         invariant: impl InvariantFn,
+        transitions: &Transitions,
         prune: impl PruneFn,
         goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
     ) -> Result<SearchLog, SearchError> {
         let mut log = SearchLog::new();
+        let mut sleeping = SleepCache::default();
+        let mut clock = SearchClock::new(&self.cfg);
 
-        let mut queue: VecDeque<StateTrace> = start.into_iter().collect();
+        let mut queue: VecDeque<Entry> = start.into_iter().map(Entry::start).collect();
+        let mut snapshots = Snapshots::new(&self.cfg);
+        let mut budget = SearchBudget::new(&self.cfg);
+        let mut graph = FairnessGraph::new(&self.cfg);
+        let mut violations = Violations::new(&self.cfg);
 
         let mut last_prune = None;
         let mut last_already_meet = None;
 
         let mut goal_achieved = false;
-        while let Some(v) = queue.pop_front() {
-            log.visited_total += 1;
-            let state = SearchState::from_trace(&v).map_err(|k| SearchError::new(k, &log))?;
-            let system = state.system.handle();
-            let h = system.hash();
-            let already_meet = !visited.insert(h);
-            if !already_meet {
-                log.visited_unique += 1;
+        while let Some(Entry {
+            trace: mut v,
+            sleep,
+            mut monitor,
+            edge,
+            parent,
+            prev,
+            snapshot,
+        }) = queue.pop_front()
+        {
+            clock.tick(&mut log, queue.len());
+            let state = snapshots
+                .restore(snapshot, &v, &mut log)
+                .map_err(|k| violations.stop(SearchError::new(k, &log)))?;
+            budget
+                .check(&log, &v, &state)
+                .map_err(|err| violations.stop(err))?;
+            log.visit(&v);
+
+            // branch on the other choices made by the last step
+            let choices = state.branch_choices(&mut v);
+            log.choose(&choices);
+            for u in choices {
+                queue.push_back(Entry {
+                    trace: u,
+                    sleep: sleep.clone(),
+                    monitor: monitor.clone(),
+                    edge,
+                    parent,
+                    prev: prev.clone(),
+                    snapshot: None,
+                });
             }
+            let system = state.system.handle();
 
             // make state view
             let view = StateView::new(&state, v.clone());
 
-            // check invariant
-            invariant(view.clone()).map_err(|report| {
+            // update monitor of the properties
+            let property_check = monitor.step(&self.cfg.properties, &view);
+
+            let h = monitor.hash(abstraction(view.clone()));
+            let visit = sleep.visit(visited, &mut sleeping, h);
+            if visit == Visit::New {
+                log.visited_unique += 1;
+            }
+            let already_meet = visit == Visit::Explored;
+            let node = graph.visit(edge, h, system.hash(), &v, monitor.obligations());
+            self.graph.visit(parent, &v, h);
+
+            // check invariant and the transition from the previous state
+            let transition = transitions.check(prev.as_deref(), &view);
+            snapshots.release(prev);
+            if let Err(report) = invariant(view.clone()).and(transition) {
                 let err = InvariantViolation {
                     trace: v.clone(),
                     log: system.log(),
                     report,
                 };
-                let kind = SearchErrorKind::InvariantViolation(err);
-                SearchError::new(kind, &log)
+                self.graph.mark(h, NodeMark::Violation);
+                violations.add(err, h, &log)?;
+                continue;
+            }
+
+            // check properties
+            property_check.map_err(|property| {
+                self.graph.mark(h, NodeMark::Violation);
+                let err = PropertyViolation::new(property, v.clone(), system.log(), None);
+                let kind = SearchErrorKind::PropertyViolation(err);
+                violations.stop(SearchError::new(kind, &log))
             })?;
 
             // check goal achieved
             let goal_check_result = goal(view.clone());
             if goal_check_result.is_ok() {
+                if let Some(property) = monitor.pending(&self.cfg.properties) {
+                    self.graph.mark(h, NodeMark::Violation);
+                    let err = PropertyViolation::new(property, v, system.log(), None);
+                    let err = SearchErrorKind::PropertyViolation(err);
+                    return Err(violations.stop(SearchError::new(err, &log)));
+                }
+                self.graph.mark(h, NodeMark::Goal);
+                graph.exclude(node);
                 goal_achieved = true;
                 continue;
             }
 
             // check prune
             if prune(view) {
+                log.pruned += 1;
+                self.graph.mark(h, NodeMark::Pruned);
+                graph.exclude(node);
                 last_prune = Some(AllPruned::new(v.clone(), system.log()));
                 continue;
             }
@@ -82,10 +167,24 @@ impl Searcher for BfsSearcher {
             // error if no transitions available
             let steps = state.gen.borrow().steps(system.clone(), &self.cfg);
             if steps.is_empty() {
+                self.graph.mark(h, NodeMark::Terminal);
+                if let Some(property) = monitor.pending(&self.cfg.properties) {
+                    let err = PropertyViolation::new(property, v, system.log(), None);
+                    let err = SearchErrorKind::PropertyViolation(err);
+                    return Err(violations.stop(SearchError::new(err, &log)));
+                }
                 let err = LivenessViolation::new(v, system.log(), goal_check_result.unwrap_err());
                 let err = SearchErrorKind::LivenessViolation(err);
                 let err = SearchError::new(err, &log);
-                return Err(err);
+                return Err(violations.stop(err));
+            }
+
+            // check properties obligations are satisfied in the cycle
+            if let Some((property, start)) = monitor.lasso(&self.cfg.properties, h) {
+                self.graph.mark(h, NodeMark::Violation);
+                let err = PropertyViolation::new(property, v, system.log(), Some(start));
+                let err = SearchErrorKind::PropertyViolation(err);
+                return Err(violations.stop(SearchError::new(err, &log)));
             }
 
             // check already meet condition
@@ -94,15 +193,55 @@ impl Searcher for BfsSearcher {
                 continue;
             }
 
-            // branch
-            steps
-                .iter()
-                .map(|s| {
+            // check depth budget
+            if budget.depth_exceeded(&v, &state) {
+                continue;
+            }
+
+            monitor.push(h, v.depth());
+            graph.enable(node, &state.gen.borrow(), &steps);
+
+            // branch, the first child can be resumed from the state snapshot
+            // or the state is kept as the previous state of the children
+            let first_child = queue.len();
+            let children = sleep.branch(&self.cfg, &state.gen.borrow(), steps);
+            log.branch(children.iter().map(|(s, _)| s));
+            children
+                .into_iter()
+                .map(|(s, sleep)| {
+                    let edge = graph.edge(node, &state.gen.borrow(), &s);
                     let mut u = v.clone();
-                    u.add_step(s.clone());
-                    u
+                    u.add_step(s);
+                    Entry {
+                        trace: u,
+                        sleep,
+                        monitor: monitor.clone(),
+                        edge,
+                        parent: Some(h),
+                        ..Default::default()
+                    }
                 })
                 .for_each(|u| queue.push_back(u));
+            if queue.len() > first_child {
+                let (prev, snapshot) = transitions.keep(state, &mut snapshots);
+                queue
+                    .range_mut(first_child..)
+                    .for_each(|child| child.prev = prev.clone());
+                queue[first_child].snapshot = snapshot;
+            }
+        }
+
+        violations.finish(&log)?;
+        budget.finish(&log)?;
+
+        if let Some(err) = graph.pending_cycle(&self.cfg) {
+            let err = SearchErrorKind::PropertyViolation(err);
+            return Err(SearchError::new(err, &log));
+        }
+
+        if let Some(cycle) = graph.fair_cycle(&self.cfg) {
+            let err = SearchErrorKind::FairCycle(Box::new(cycle));
+            return Err(SearchError::new(err, &log));
         }
 
         if goal_achieved {
@@ -111,6 +250,9 @@ impl Searcher for BfsSearcher {
             let err = SearchErrorKind::AllPruned(last_prune);
             let err = SearchError::new(err, &log);
             Err(err)
+        } else if !self.cfg.fairness.is_empty() {
+            // all cycles are unfair
+            Ok(log)
         } else {
             let err = SearchErrorKind::Cycled(last_already_meet.unwrap());
             let err = SearchError::new(err, &log);
@@ -121,30 +263,67 @@ impl Searcher for BfsSearcher {
     fn collect(
         &mut self,
         start: Vec<StateTrace>,
-        visited: &mut HashSet<HashType>,
+        visited: &mut dyn VisitedSet,
         invariant: impl InvariantFn,
+        transitions: &Transitions,
         prune: impl PruneFn,
         goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
     ) -> Result<CollectInfo, SearchError> {
         let mut log = SearchLog::new();
+        let mut sleeping = SleepCache::default();
+        let mut clock = SearchClock::new(&self.cfg);
         let mut collected = Vec::new();
-        let mut queue: VecDeque<StateTrace> = start.into_iter().collect();
+        let mut queue: VecDeque<Entry> = start.into_iter().map(Entry::start).collect();
+        let mut snapshots = Snapshots::new(&self.cfg);
+        let mut budget = SearchBudget::new(&self.cfg);
 
-        while let Some(v) = queue.pop_front() {
-            log.visited_total += 1;
-            let state = SearchState::from_trace(&v).map_err(|k| SearchError::new(k, &log))?;
-            let system = state.system.handle();
-            let h = system.hash();
-            let already_meet = !visited.insert(h);
-            if !already_meet {
-                log.visited_unique += 1;
+        while let Some(Entry {
+            trace: mut v,
+            sleep,
+            parent,
+            prev,
+            snapshot,
+            ..
+        }) = queue.pop_front()
+        {
+            clock.tick(&mut log, queue.len());
+            let state = snapshots
+                .restore(snapshot, &v, &mut log)
+                .map_err(|k| SearchError::new(k, &log))?;
+            budget.check(&log, &v, &state)?;
+            log.visit(&v);
+
+            // branch on the other choices made by the last step
+            let choices = state.branch_choices(&mut v);
+            log.choose(&choices);
+            for u in choices {
+                queue.push_back(Entry {
+                    trace: u,
+                    sleep: sleep.clone(),
+                    parent,
+                    prev: prev.clone(),
+                    ..Default::default()
+                });
             }
+            let system = state.system.handle();
 
             // make state view
             let view = StateView::new(&state, v.clone());
 
-            // check invariant
-            invariant(view.clone()).map_err(|report| {
+            let h = abstraction(view.clone());
+            let visit = sleep.visit(visited, &mut sleeping, h);
+            if visit == Visit::New {
+                log.visited_unique += 1;
+            }
+            let already_meet = visit == Visit::Explored;
+            self.graph.visit(parent, &v, h);
+
+            // check invariant and the transition from the previous state
+            let transition = transitions.check(prev.as_deref(), &view);
+            snapshots.release(prev);
+            invariant(view.clone()).and(transition).map_err(|report| {
+                self.graph.mark(h, NodeMark::Violation);
                 let err = InvariantViolation {
                     trace: v.clone(),
                     log: system.log(),
@@ -155,28 +334,60 @@ impl Searcher for BfsSearcher {
             })?;
 
             // check prune and already meet condition
-            if prune(view.clone()) || already_meet {
+            if prune(view.clone()) {
+                log.pruned += 1;
+                self.graph.mark(h, NodeMark::Pruned);
+                continue;
+            }
+            if already_meet {
                 continue;
             }
 
             // check goal achieved
             if goal(view).is_ok() {
+                self.graph.mark(h, NodeMark::Goal);
                 collected.push(v);
                 continue;
             }
 
-            // branch
+            // check depth budget
+            if budget.depth_exceeded(&v, &state) {
+                continue;
+            }
+
+            // branch, the first child can be resumed from the state snapshot
+            // or the state is kept as the previous state of the children
             let steps = state.gen.borrow().steps(system, &self.cfg);
-            steps
-                .iter()
-                .map(|s| {
+            if steps.is_empty() {
+                self.graph.mark(h, NodeMark::Terminal);
+            }
+            let first_child = queue.len();
+            let children = sleep.branch(&self.cfg, &state.gen.borrow(), steps);
+            log.branch(children.iter().map(|(s, _)| s));
+            children
+                .into_iter()
+                .map(|(s, sleep)| {
                     let mut u = v.clone();
-                    u.add_step(s.clone());
-                    u
+                    u.add_step(s);
+                    Entry {
+                        trace: u,
+                        sleep,
+                        parent: Some(h),
+                        ..Default::default()
+                    }
                 })
                 .for_each(|u| queue.push_back(u));
+            if queue.len() > first_child {
+                let (prev, snapshot) = transitions.keep(state, &mut snapshots);
+                queue
+                    .range_mut(first_child..)
+                    .for_each(|child| child.prev = prev.clone());
+                queue[first_child].snapshot = snapshot;
+            }
         }
 
+        budget.finish(&log)?;
+
         Ok(CollectInfo {
             states: collected,
             log,
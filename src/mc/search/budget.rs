@@ -0,0 +1,68 @@
+//! Budgets of the search (see [`SearchConfig`]).
+
+use std::time::Instant;
+
+use super::{
+    config::SearchConfig,
+    error::{Budget, BudgetExceeded, SearchError, SearchErrorKind},
+    log::SearchLog,
+    state::{SearchState, StateTrace},
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+pub(crate) struct SearchBudget {
+    cfg: SearchConfig,
+    start: Instant,
+    depth_exceeded: Option<BudgetExceeded>,
+}
+
+impl SearchBudget {
+    pub fn new(cfg: &SearchConfig) -> Self {
+        Self {
+            cfg: cfg.clone(),
+            start: Instant::now(),
+            depth_exceeded: None,
+        }
+    }
+
+    /// Returns error if the states or time budget is exhausted,
+    /// so the state can not be visited.
+    pub fn check(
+        &self,
+        log: &SearchLog,
+        trace: &StateTrace,
+        state: &SearchState,
+    ) -> Result<(), SearchError> {
+        let budget = match (self.cfg.max_visited_states, self.cfg.time_limit) {
+            (Some(states), _) if log.visited_total >= states => Budget::VisitedStates(states),
+            (_, Some(limit)) if self.start.elapsed() > limit => Budget::Time(limit),
+            _ => return Ok(()),
+        };
+        let log_of_state = state.system.handle().log();
+        let err = BudgetExceeded::new(budget, trace.clone(), log_of_state);
+        Err(SearchError::new(SearchErrorKind::BudgetExceeded(err), log))
+    }
+
+    /// Returns true if the state can not be branched because of the max depth.
+    /// The search continues, but it will end with error (see [`SearchBudget::finish`]).
+    pub fn depth_exceeded(&mut self, trace: &StateTrace, state: &SearchState) -> bool {
+        let Some(max_depth) = self.cfg.max_depth.filter(|d| trace.depth() >= *d) else {
+            return false;
+        };
+        if self.depth_exceeded.is_none() {
+            let log = state.system.handle().log();
+            let err = BudgetExceeded::new(Budget::Depth(max_depth), trace.clone(), log);
+            self.depth_exceeded = Some(err);
+        }
+        true
+    }
+
+    /// Returns error if some state was not branched because of the max depth.
+    pub fn finish(self, log: &SearchLog) -> Result<(), SearchError> {
+        match self.depth_exceeded {
+            Some(err) => Err(SearchError::new(SearchErrorKind::BudgetExceeded(err), log)),
+            None => Ok(()),
+        }
+    }
+}
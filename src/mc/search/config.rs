@@ -1,3 +1,12 @@
+use std::{sync::Arc, time::Duration};
+
+use super::{
+    fairness::Fairness,
+    log::{Progress, SearchLog},
+    property::Property,
+    workload::Workload,
+};
+
 /// Config for the current search iteration.
 #[derive(Clone)]
 pub struct SearchConfig {
@@ -7,11 +16,85 @@ pub struct SearchConfig {
     /// Max number of node shutdown injected during the search
     pub max_node_shutdown: Option<usize>,
 
-    /// Max number of disk faults injected during the search
+    /// Max number of restarts of the shutdown nodes injected during the search.
+    /// Only nodes with the role, for which process factory is registered
+    /// (see [`crate::model::SystemHandle::register_process_factory`]), can be restarted.
+    /// Nodes are not restarted if not specified.
+    pub max_node_restarts: Option<usize>,
+
+    /// Max number of disk faults injected during the search.
+    /// Disk faults are not injected if not specified.
     pub max_disk_faults: Option<usize>,
 
     /// Max number of UDP msg drops injected during the search
     pub max_msg_drops: Option<usize>,
+
+    /// Max number of network partitions injected during the search.
+    /// Nodes are split into two groups, splits which differ
+    /// only by the nodes with the same role are considered the same.
+    /// Partitions are not injected if not specified.
+    pub max_partitions: Option<usize>,
+
+    /// Max number of system snapshots kept by the searcher at the same time.
+    /// Child state is resumed from the snapshot of the parent state
+    /// instead of replaying the whole trace. The system can not be cloned,
+    /// so only one child of every state is resumed, the other children are replayed.
+    /// If there are transition invariants, the parent state is kept
+    /// as the previous state of the transitions to its children instead,
+    /// so the children are not resumed from it.
+    /// Snapshots are not used if not specified.
+    pub max_snapshots: Option<usize>,
+
+    /// Skip interleavings of the independent events using sleep sets.
+    /// UDP messages and timers are independent if they are handled by different processes.
+    /// TCP, RPC and file system events, node faults, partitions, apply functions
+    /// and workload messages are dependent with all events.
+    pub partial_order_reduction: bool,
+
+    /// Max depth of the explored traces.
+    /// Deeper states are checked, but not branched.
+    ///
+    /// Depth is the number of the steps in the trace, so it also counts
+    /// the build step and the steps made by [`crate::mc::ModelChecker::apply`]
+    /// and the previous searches (see [`crate::mc::StateView::depth`]).
+    /// Required by [`crate::mc::RandomWalkSearcher`], which ends the walks at this depth.
+    pub max_depth: Option<usize>,
+
+    /// Max number of the states visited during the search.
+    pub max_visited_states: Option<usize>,
+
+    /// Max duration of the search.
+    pub time_limit: Option<Duration>,
+
+    /// Temporal properties checked along the search paths
+    /// by [`crate::mc::BfsSearcher`] and [`crate::mc::DfsSearcher`].
+    pub properties: Vec<Property>,
+
+    /// Fairness constraints of the liveness checks by [`crate::mc::BfsSearcher`]
+    /// and [`crate::mc::DfsSearcher`]. If set, the search fails if the explored states
+    /// make the fair cycle, in which the goal is not achieved,
+    /// even if the goal is achieved in the other states.
+    pub fairness: Vec<Fairness>,
+
+    /// If set, [`crate::mc::BfsSearcher`], [`crate::mc::DfsSearcher`]
+    /// and [`crate::mc::BestFirstSearcher`] do not stop on the invariant violation,
+    /// but record up to the provided number of violations with distinct reports
+    /// and states, and return them at the end of the search. If the search is stopped
+    /// by the other error, the recorded violations are returned instead of it.
+    /// States are distinguished by the same key as the visited states
+    /// (see [`crate::mc::ModelChecker::check_with_abstraction`]).
+    /// States, in which the invariant is violated, are not branched.
+    pub max_violations: Option<usize>,
+
+    /// Interval of the frontier sampling and the progress reports.
+    pub progress: Progress,
+
+    /// Client workload, messages of which are sent by the searchers
+    /// as the separate steps at any point of the search.
+    pub workload: Workload,
+
+    /// Max number of the workload messages sent along the search path.
+    pub max_local_messages: Option<usize>,
 }
 
 impl SearchConfig {
@@ -55,8 +138,21 @@ impl SearchConfig {
         Self {
             max_node_faults: None,
             max_node_shutdown: None,
+            max_node_restarts: None,
             max_disk_faults: None,
             max_msg_drops: None,
+            max_partitions: None,
+            max_snapshots: None,
+            partial_order_reduction: false,
+            max_depth: None,
+            max_visited_states: None,
+            time_limit: None,
+            properties: Vec::new(),
+            fairness: Vec::new(),
+            max_violations: None,
+            progress: Progress::default(),
+            workload: Workload::default(),
+            max_local_messages: None,
         }
     }
 }
@@ -64,12 +160,25 @@ impl SearchConfig {
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Represents builder for the search config [`SearchConfig`].
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct SearchConfigBuilder {
     max_node_faults: Option<usize>,
     max_node_shutdown: Option<usize>,
+    max_node_restarts: Option<usize>,
     max_disk_faults: Option<usize>,
     max_msg_drops: Option<usize>,
+    max_partitions: Option<usize>,
+    max_snapshots: Option<usize>,
+    partial_order_reduction: bool,
+    max_depth: Option<usize>,
+    max_visited_states: Option<usize>,
+    time_limit: Option<Duration>,
+    properties: Vec<Property>,
+    fairness: Vec<Fairness>,
+    max_violations: Option<usize>,
+    progress: Progress,
+    workload: Workload,
+    max_local_messages: Option<usize>,
 }
 
 impl SearchConfigBuilder {
@@ -90,6 +199,12 @@ impl SearchConfigBuilder {
         self
     }
 
+    #[allow(missing_docs)]
+    pub fn max_node_restarts(mut self, max_node_restarts: usize) -> Self {
+        self.max_node_restarts = Some(max_node_restarts);
+        self
+    }
+
     #[allow(missing_docs)]
     pub fn max_disk_faults(mut self, max_disk_faults: usize) -> Self {
         self.max_disk_faults = Some(max_disk_faults);
@@ -102,6 +217,98 @@ impl SearchConfigBuilder {
         self
     }
 
+    #[allow(missing_docs)]
+    pub fn max_partitions(mut self, max_partitions: usize) -> Self {
+        self.max_partitions = Some(max_partitions);
+        self
+    }
+
+    #[allow(missing_docs)]
+    pub fn max_snapshots(mut self, max_snapshots: usize) -> Self {
+        self.max_snapshots = Some(max_snapshots);
+        self
+    }
+
+    #[allow(missing_docs)]
+    pub fn partial_order_reduction(mut self, enabled: bool) -> Self {
+        self.partial_order_reduction = enabled;
+        self
+    }
+
+    #[allow(missing_docs)]
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    #[allow(missing_docs)]
+    pub fn max_visited_states(mut self, max_visited_states: usize) -> Self {
+        self.max_visited_states = Some(max_visited_states);
+        self
+    }
+
+    #[allow(missing_docs)]
+    pub fn time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Add temporal property checked during the search.
+    pub fn property(mut self, property: Property) -> Self {
+        self.properties.push(property);
+        self
+    }
+
+    /// Add fairness constraint of the liveness checks.
+    pub fn fairness(mut self, fairness: Fairness) -> Self {
+        self.fairness.push(fairness);
+        self
+    }
+
+    /// Keep going after the invariant violations
+    /// until the provided number of them is found.
+    pub fn keep_going(mut self, max_violations: usize) -> Self {
+        self.max_violations = Some(max_violations);
+        self
+    }
+
+    /// Sample the search frontier and call the provided function
+    /// with the current search log once per interval.
+    pub fn progress(
+        mut self,
+        interval: Duration,
+        report: impl Fn(&SearchLog) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Progress::new(interval, Some(Arc::new(report)));
+        self
+    }
+
+    /// Print the current search log to the stderr once per interval.
+    pub fn progress_stderr(self, interval: Duration) -> Self {
+        self.progress(interval, |log| {
+            eprintln!(
+                "[{:.2?}] {:.0} states/s, frontier: {}, collision probability: {:.1e}\n{}",
+                log.elapsed,
+                log.states_per_second(),
+                log.frontier.last().map(|(_, n)| *n).unwrap_or(0),
+                log.collision_probability(),
+                log
+            )
+        })
+    }
+
+    /// Set client workload sent by the searchers.
+    pub fn workload(mut self, workload: Workload) -> Self {
+        self.workload = workload;
+        self
+    }
+
+    #[allow(missing_docs)]
+    pub fn max_local_messages(mut self, max_local_messages: usize) -> Self {
+        self.max_local_messages = Some(max_local_messages);
+        self
+    }
+
     #[allow(missing_docs)]
     pub fn no_faults() -> Self {
         Self::new()
@@ -120,8 +327,21 @@ impl SearchConfigBuilder {
         SearchConfig {
             max_node_faults: self.max_node_faults,
             max_node_shutdown: self.max_node_shutdown,
+            max_node_restarts: self.max_node_restarts,
             max_disk_faults: self.max_disk_faults,
             max_msg_drops: self.max_msg_drops,
+            max_partitions: self.max_partitions,
+            max_snapshots: self.max_snapshots,
+            partial_order_reduction: self.partial_order_reduction,
+            max_depth: self.max_depth,
+            max_visited_states: self.max_visited_states,
+            time_limit: self.time_limit,
+            properties: self.properties,
+            fairness: self.fairness,
+            max_violations: self.max_violations,
+            progress: self.progress,
+            workload: self.workload,
+            max_local_messages: self.max_local_messages,
         }
     }
 }
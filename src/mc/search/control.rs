@@ -1,4 +1,9 @@
-use crate::{mc::StateView, model::system::SystemHandle};
+use crate::{
+    mc::StateView,
+    model::system::{HashType, SystemHandle},
+};
+
+use super::step::StateTraceStep;
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -12,6 +17,25 @@ impl<F> InvariantFn for F where
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Check the system model invariants over the transitions between states
+/// (see [crate::mc::ModelChecker::transition_invariant]).
+/// Receives the previous state, the next state and the step between them.
+pub trait TransitionInvariantFn:
+    Fn(StateView, StateView, &StateTraceStep) -> Result<(), String> + Send + Sync + Clone + 'static
+{
+}
+
+impl<F> TransitionInvariantFn for F where
+    F: Fn(StateView, StateView, &StateTraceStep) -> Result<(), String>
+        + Send
+        + Sync
+        + Clone
+        + 'static
+{
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// Allows to prune not relevant states.
 pub trait PruneFn: Fn(StateView) -> bool + Send + Sync + Clone + 'static {}
 
@@ -27,6 +51,28 @@ impl<F> GoalFn for F where F: Fn(StateView) -> Result<(), String> + Send + Sync
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Maps the system model state to the key, by which the visited states are deduplicated
+/// (see [crate::mc::ModelChecker::check_with_abstraction]).
+/// States with the same key are considered the same.
+pub trait AbstractionFn: Fn(StateView) -> HashType + Send + Sync + Clone + 'static {}
+
+impl<F> AbstractionFn for F where F: Fn(StateView) -> HashType + Send + Sync + Clone + 'static {}
+
+/// Default abstraction, which keys the state by the hash of the system model.
+pub(crate) fn system_hash(view: StateView) -> HashType {
+    view.system().hash()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Scores the system model state, children of the states with the greater score
+/// are explored first (see [crate::mc::BestFirstSearcher]).
+pub trait ScoreFn: Fn(StateView) -> i64 + Send + Sync + Clone + 'static {}
+
+impl<F> ScoreFn for F where F: Fn(StateView) -> i64 + Send + Sync + Clone + 'static {}
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// Allows to make some actions with the system model.
 /// For example, it can be used to send local messages to process,
 /// or crash some node.
@@ -37,6 +83,7 @@ impl<F> ApplyFn for F where F: Fn(SystemHandle) + Send + Sync + Clone + 'static
 pub trait ApplyFunctor: Send + Sync {
     fn apply(&self, sys: SystemHandle);
     fn clone(&self) -> Box<dyn ApplyFunctor>;
+    fn name(&self) -> &str;
 }
 
 impl Clone for Box<dyn ApplyFunctor> {
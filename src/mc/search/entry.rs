@@ -0,0 +1,55 @@
+//! Entries of the searchers frontiers.
+
+use std::rc::Rc;
+
+use crate::model::system::HashType;
+
+use super::{
+    fairness::EdgeId,
+    por::SleepSet,
+    property::PathMonitor,
+    state::{SearchState, StateTrace},
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// State of the search, which can be resumed from the snapshot.
+///
+/// The system model can not be cloned, so only one child of the expanded state
+/// gets its snapshot, while the other children are replayed from their traces.
+/// If there are transition invariants, the expanded state is shared
+/// by the children as their previous state instead, so none of them
+/// is resumed from it (see `Transitions::keep`).
+#[derive(Default)]
+pub(crate) struct Entry {
+    /// Trace of the state.
+    pub trace: StateTrace,
+
+    /// Steps, which are not explored from the state.
+    pub sleep: SleepSet,
+
+    /// Properties monitor of the path to the state.
+    pub monitor: PathMonitor,
+
+    /// Edge of the fairness graph, which leads to the state.
+    pub edge: Option<EdgeId>,
+
+    /// Hash of the parent state in the recorded search graph.
+    pub parent: Option<HashType>,
+
+    /// Previous state of the transition invariants, shared by the siblings.
+    pub prev: Option<Rc<SearchState>>,
+
+    /// Snapshot of the parent state, from which the state is resumed.
+    pub snapshot: Option<SearchState>,
+}
+
+impl Entry {
+    /// Make entry of the start state of the search.
+    pub fn start(trace: StateTrace) -> Self {
+        Self {
+            trace,
+            ..Default::default()
+        }
+    }
+}
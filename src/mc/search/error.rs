@@ -1,11 +1,17 @@
 //! Errors which can in the system model during the search.
 
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{Debug, Display},
+    path::Path,
+    time::Duration,
+};
 
 use crate::{model::log::Log, HashType};
 
 use super::{log::SearchLog, state::StateTrace};
 
+pub use crate::util::replay::ReplayError;
+
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Some process panic.
@@ -188,12 +194,225 @@ impl Display for Cycled {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Cycle of the states, in which the goal is not achieved,
+/// and which satisfies the fairness constraints (see [`crate::mc::Fairness`]).
+#[derive(Clone)]
+pub struct FairCycle {
+    /// Trace to the first state of the cycle.
+    pub prefix: StateTrace,
+
+    /// Steps of the cycle, which follow the prefix
+    /// and lead back to the state with the same hash.
+    pub cycle: StateTrace,
+
+    /// Log of system events
+    pub log: Log,
+
+    /// Hash of the first state of the cycle.
+    pub hash: HashType,
+}
+
+impl FairCycle {
+    pub(crate) fn new(prefix: StateTrace, cycle: StateTrace, log: Log, hash: HashType) -> Self {
+        Self {
+            prefix,
+            cycle,
+            log,
+            hash,
+        }
+    }
+}
+
+impl Debug for FairCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for FairCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Found fair cycle in which the goal is not achieved.")?;
+        writeln!(f, "======== PREFIX =======")?;
+        write!(f, "{}", self.prefix)?;
+        writeln!(f, "======== CYCLE ========")?;
+        write!(f, "{}", self.cycle)?;
+        writeln!(f, "========= LOG =========")?;
+        writeln!(f, "{}", self.log)?;
+        write!(f, "State hash: {}", self.hash)?;
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Temporal property violation (see [`crate::mc::Property`]).
+#[derive(Clone)]
+pub struct PropertyViolation {
+    /// Name of the violated property.
+    pub property: String,
+
+    /// Failure trace
+    pub trace: StateTrace,
+
+    /// Log of system events
+    pub log: Log,
+
+    /// If set, the trace ends with the cycle, which starts after
+    /// the provided number of steps, and the property obligation
+    /// is never satisfied in the cycle.
+    pub cycle_start: Option<usize>,
+}
+
+impl PropertyViolation {
+    pub(crate) fn new(
+        property: String,
+        trace: StateTrace,
+        log: Log,
+        cycle_start: Option<usize>,
+    ) -> Self {
+        Self {
+            property,
+            trace,
+            log,
+            cycle_start,
+        }
+    }
+}
+
+impl Debug for PropertyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for PropertyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Property violation: {}.", self.property)?;
+        if let Some(start) = self.cycle_start {
+            writeln!(f, "Trace ends with cycle starting after step {}.", start)?;
+        }
+        writeln!(f, "======== TRACE ========")?;
+        write!(f, "{}", self.trace)?;
+        writeln!(f, "========= LOG =========")?;
+        write!(f, "{}", self.log)?;
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Search budget (see [`crate::mc::SearchConfig`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Budget {
+    /// Max depth of the trace.
+    Depth(usize),
+
+    /// Max number of the visited states.
+    VisitedStates(usize),
+
+    /// Search time limit.
+    Time(Duration),
+}
+
+impl Display for Budget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Budget::Depth(depth) => write!(f, "max depth {}", depth),
+            Budget::VisitedStates(states) => write!(f, "max visited states {}", states),
+            Budget::Time(limit) => write!(f, "time limit {:?}", limit),
+        }
+    }
+}
+
+/// Search budget is exhausted, so not all states were checked.
+/// Search log of the error contains statistics gathered before.
+#[derive(Clone)]
+pub struct BudgetExceeded {
+    /// Exhausted budget.
+    pub budget: Budget,
+
+    /// Trace of the state, in which the budget was exhausted.
+    pub trace: StateTrace,
+
+    /// Log of system events
+    pub log: Log,
+}
+
+impl BudgetExceeded {
+    pub(crate) fn new(budget: Budget, trace: StateTrace, log: Log) -> Self {
+        Self { budget, trace, log }
+    }
+}
+
+impl Debug for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Search budget exceeded: {}.", self.budget)?;
+        writeln!(f, "======== TRACE ========")?;
+        write!(f, "{}", self.trace)?;
+        writeln!(f, "========= LOG =========")?;
+        write!(f, "{}", self.log)?;
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// No state, reached along the schedule prefix, has the step
+/// matching the next constraint (see [`crate::mc::ModelChecker::follow`]).
+#[derive(Clone)]
+pub struct ScheduleUnmatched {
+    /// Index of the unmatched constraint in the schedule.
+    pub index: usize,
+
+    /// Description of the unmatched constraint.
+    pub constraint: String,
+
+    /// Trace of the last state, in which the constraint is not matched.
+    pub trace: StateTrace,
+
+    /// Log of system events
+    pub log: Log,
+}
+
+impl Debug for ScheduleUnmatched {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for ScheduleUnmatched {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Schedule constraint {} is not matched: {}.",
+            self.index, self.constraint
+        )?;
+        writeln!(f, "======== TRACE ========")?;
+        write!(f, "{}", self.trace)?;
+        writeln!(f, "========= LOG =========")?;
+        write!(f, "{}", self.log)?;
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// Represents search error kind.
 #[derive(Clone)]
 pub enum SearchErrorKind {
     /// Invariant violation
     InvariantViolation(InvariantViolation),
 
+    /// Distinct invariant violations found in the keep going mode
+    /// (see [`crate::mc::SearchConfig::max_violations`]).
+    InvariantViolations(Vec<InvariantViolation>),
+
     /// Liveness property violation
     LivenessViolation(LivenessViolation),
 
@@ -205,6 +424,36 @@ pub enum SearchErrorKind {
 
     /// System is in cycle.
     Cycled(Cycled),
+
+    /// Search budget is exhausted.
+    BudgetExceeded(BudgetExceeded),
+
+    /// Temporal property violation.
+    PropertyViolation(PropertyViolation),
+
+    /// Fair cycle, in which the goal is not achieved.
+    FairCycle(Box<FairCycle>),
+
+    /// Schedule prefix can not be followed.
+    ScheduleUnmatched(ScheduleUnmatched),
+}
+
+impl SearchErrorKind {
+    /// Get trace of the state, in which the error was found.
+    pub fn trace(&self) -> Option<&StateTrace> {
+        match self {
+            SearchErrorKind::InvariantViolation(e) => Some(&e.trace),
+            SearchErrorKind::InvariantViolations(e) => e.first().map(|e| &e.trace),
+            SearchErrorKind::LivenessViolation(e) => Some(&e.trace),
+            SearchErrorKind::AllPruned(e) => Some(&e.last_trace),
+            SearchErrorKind::ProcessPanic(e) => e.trace.as_ref(),
+            SearchErrorKind::Cycled(e) => Some(&e.trace),
+            SearchErrorKind::BudgetExceeded(e) => Some(&e.trace),
+            SearchErrorKind::PropertyViolation(e) => Some(&e.trace),
+            SearchErrorKind::FairCycle(e) => Some(&e.prefix),
+            SearchErrorKind::ScheduleUnmatched(e) => Some(&e.trace),
+        }
+    }
 }
 
 impl Display for SearchErrorKind {
@@ -213,12 +462,24 @@ impl Display for SearchErrorKind {
             SearchErrorKind::InvariantViolation(invariant_violation) => {
                 write!(f, "{}", invariant_violation)
             }
+            SearchErrorKind::InvariantViolations(violations) => {
+                writeln!(f, "Found {} invariant violations.", violations.len())?;
+                for (i, violation) in violations.iter().enumerate() {
+                    writeln!(f, "======== VIOLATION {} ========", i + 1)?;
+                    write!(f, "{}", violation)?;
+                }
+                Ok(())
+            }
             SearchErrorKind::LivenessViolation(liveness_violation) => {
                 write!(f, "{}", liveness_violation)
             }
             SearchErrorKind::ProcessPanic(p) => write!(f, "{}", p),
             SearchErrorKind::AllPruned(err) => write!(f, "{}", err),
             SearchErrorKind::Cycled(cycled) => write!(f, "{}", cycled),
+            SearchErrorKind::BudgetExceeded(err) => write!(f, "{}", err),
+            SearchErrorKind::PropertyViolation(err) => write!(f, "{}", err),
+            SearchErrorKind::FairCycle(err) => write!(f, "{}", err),
+            SearchErrorKind::ScheduleUnmatched(err) => write!(f, "{}", err),
         }
     }
 }
@@ -248,6 +509,15 @@ impl SearchError {
             log: log.clone(),
         }
     }
+
+    /// Save trace of the error in the JSON format into the file,
+    /// which can be replayed later with [`crate::mc::ModelChecker::replay`].
+    pub fn save_trace(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        match self.kind.trace() {
+            Some(trace) => trace.save(path),
+            None => Err(std::io::Error::other("error has no trace")),
+        }
+    }
 }
 
 impl Display for SearchError {
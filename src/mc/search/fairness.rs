@@ -0,0 +1,515 @@
+//! Fairness constraints of the liveness checks
+//! (see [`crate::mc::SearchConfigBuilder::fairness`]).
+//!
+//! With fairness constraints the searcher records the graph of the explored states
+//! and reports the cycle, in which the goal is not achieved, only if there is
+//! the run through it which satisfies all constraints.
+//! The graph is also recorded with the temporal properties, so the cycles,
+//! in which the property obligation is never satisfied, are found
+//! regardless of the paths, by which their states are explored.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    mem::discriminant,
+};
+
+use crate::{model::system::HashType, Address};
+
+use super::{
+    config::SearchConfig,
+    error::{FairCycle, PropertyViolation},
+    gen::Generator,
+    state::{SearchState, StateTrace},
+    step::StateTraceStep,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Kind of the events, to which the fairness constraint is applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FairEvent {
+    /// Delivery of the udp messages.
+    Udp,
+    /// Timers.
+    Timer,
+    /// Tcp packets and events.
+    Tcp,
+    /// Rpc messages and events.
+    Rpc,
+    /// File system events.
+    Fs,
+}
+
+impl FairEvent {
+    /// Fault steps, which drop or fail the event, are not considered as fair.
+    fn of(step: &StateTraceStep) -> Option<Self> {
+        match step.inner() {
+            StateTraceStep::SelectUdp(_, msg) if !msg.drop => Some(Self::Udp),
+            StateTraceStep::SelectTimer(..) => Some(Self::Timer),
+            StateTraceStep::SelectTcpPacket(..) | StateTraceStep::SelectTcpEvent(..) => {
+                Some(Self::Tcp)
+            }
+            StateTraceStep::SelectRpcMessage(..) | StateTraceStep::SelectRpcEvent(..) => {
+                Some(Self::Rpc)
+            }
+            StateTraceStep::SelectFsEvent(..) => Some(Self::Fs),
+            _ => None,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Fairness constraint on the runs of the system.
+///
+/// Constraint is applied to the steps, which select events of the provided kind
+/// and targeted to the provided process. If the kind or the process is not set,
+/// then the events of any kind or any process are matched.
+/// Message drops and file system failures are never matched, so the strong
+/// fairness of the messages delivery excludes runs, in which messages are always dropped.
+#[derive(Clone, Debug)]
+pub struct Fairness {
+    strong: bool,
+    event: Option<FairEvent>,
+    process: Option<Address>,
+}
+
+impl Fairness {
+    /// Weak fairness: if matched step is available continuously,
+    /// then it is eventually taken.
+    pub fn weak() -> Self {
+        Self {
+            strong: false,
+            event: None,
+            process: None,
+        }
+    }
+
+    /// Strong fairness: if matched step is available infinitely often,
+    /// then it is taken infinitely often.
+    pub fn strong() -> Self {
+        Self {
+            strong: true,
+            ..Self::weak()
+        }
+    }
+
+    /// Apply constraint only to the events of provided kind.
+    pub fn events(mut self, event: FairEvent) -> Self {
+        self.event = Some(event);
+        self
+    }
+
+    /// Apply constraint only to the events targeted to the provided process.
+    pub fn process(mut self, process: Address) -> Self {
+        self.process = Some(process);
+        self
+    }
+
+    fn matches(&self, step: &StateTraceStep, gen: &Generator) -> bool {
+        let (Some(id), Some(event)) = (step.event_id(), FairEvent::of(step)) else {
+            return false;
+        };
+        self.event.is_none_or(|e| e == event)
+            && self
+                .process
+                .as_ref()
+                .is_none_or(|p| gen.event_target(id) == Some(p))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+struct Edge {
+    step: StateTraceStep,
+    taken: Vec<bool>, // constraints, which steps are taken by the edge
+    to: Option<usize>,
+}
+
+struct Node {
+    trace: StateTrace,
+    system_hash: HashType,
+    excluded: bool,     // goal is achieved or state is pruned
+    pending: Vec<bool>, // properties, which obligations are not satisfied in the state
+    enabled: Vec<bool>, // constraints, which steps are available in the state
+    edges: Vec<Edge>,
+}
+
+/// Id of the state in the graph and index of its explored step.
+pub(crate) type EdgeId = (usize, usize);
+
+enum Waypoint {
+    Edge(usize, usize),
+    Node(usize),
+}
+
+/// Graph of the states explored by the search.
+/// Is recorded only if the fairness constraints or the temporal properties are set.
+pub(crate) struct FairnessGraph {
+    record: bool,
+    constraints: Vec<Fairness>,
+    nodes: Vec<Node>,
+    index: HashMap<HashType, usize>,
+}
+
+impl FairnessGraph {
+    pub fn new(cfg: &SearchConfig) -> Self {
+        Self {
+            record: !cfg.fairness.is_empty() || !cfg.properties.is_empty(),
+            constraints: cfg.fairness.clone(),
+            nodes: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Records visit of the state with provided hash by the edge.
+    /// Returns id of the state in the graph.
+    pub fn visit(
+        &mut self,
+        edge: Option<EdgeId>,
+        hash: HashType,
+        system_hash: HashType,
+        trace: &StateTrace,
+        pending: Vec<bool>,
+    ) -> Option<usize> {
+        if !self.record {
+            return None;
+        }
+        let id = *self.index.entry(hash).or_insert_with(|| {
+            self.nodes.push(Node {
+                trace: trace.clone(),
+                system_hash,
+                excluded: false,
+                pending,
+                enabled: vec![false; self.constraints.len()],
+                edges: Vec::new(),
+            });
+            self.nodes.len() - 1
+        });
+        if let Some((u, e)) = edge {
+            self.nodes[u].edges[e].to = Some(id);
+        }
+        Some(id)
+    }
+
+    /// Marks state as the one, which can not be the part of the fair cycle.
+    pub fn exclude(&mut self, node: Option<usize>) {
+        if let Some(u) = node {
+            self.nodes[u].excluded = true;
+        }
+    }
+
+    /// Records steps available in the state.
+    pub fn enable(&mut self, node: Option<usize>, gen: &Generator, steps: &[StateTraceStep]) {
+        let Some(u) = node else {
+            return;
+        };
+        for (i, c) in self.constraints.iter().enumerate() {
+            self.nodes[u].enabled[i] |= steps.iter().any(|s| c.matches(s, gen));
+        }
+    }
+
+    /// Records step explored from the state.
+    /// Returns edge, which must be provided on visit of the next state.
+    pub fn edge(
+        &mut self,
+        node: Option<usize>,
+        gen: &Generator,
+        step: &StateTraceStep,
+    ) -> Option<EdgeId> {
+        let u = node?;
+        let taken = self
+            .constraints
+            .iter()
+            .map(|c| c.matches(step, gen))
+            .collect();
+        let edges = &mut self.nodes[u].edges;
+        edges.push(Edge {
+            step: step.clone(),
+            taken,
+            to: None,
+        });
+        Some((u, edges.len() - 1))
+    }
+
+    /// Returns the lasso, which ends with the fair cycle of the states,
+    /// in which the goal is not achieved.
+    pub fn fair_cycle(&self, cfg: &SearchConfig) -> Option<FairCycle> {
+        if self.constraints.is_empty() {
+            return None;
+        }
+        let allowed: Vec<bool> = self.nodes.iter().map(|n| !n.excluded).collect();
+        self.lasso(allowed, cfg)
+    }
+
+    /// Returns the property violation, which trace ends with the fair cycle of the states,
+    /// in which the property obligation is never satisfied.
+    pub fn pending_cycle(&self, cfg: &SearchConfig) -> Option<PropertyViolation> {
+        cfg.properties.iter().enumerate().find_map(|(i, property)| {
+            let allowed = self
+                .nodes
+                .iter()
+                .map(|n| !n.excluded && n.pending[i])
+                .collect();
+            let lasso = self.lasso(allowed, cfg)?;
+            let mut trace = lasso.prefix.clone();
+            lasso
+                .cycle
+                .steps()
+                .iter()
+                .for_each(|s| trace.add_step(s.clone()));
+            let start = lasso.prefix.depth();
+            let name = property.name().to_string();
+            Some(PropertyViolation::new(name, trace, lasso.log, Some(start)))
+        })
+    }
+
+    /// Returns the lasso, which ends with the fair cycle of the allowed states.
+    fn lasso(&self, allowed: Vec<bool>, cfg: &SearchConfig) -> Option<FairCycle> {
+        let scc = self.fair_scc(allowed)?;
+        let start = scc
+            .iter()
+            .enumerate()
+            .filter(|(_, in_scc)| **in_scc)
+            .min_by_key(|(u, _)| self.nodes[*u].trace.depth())
+            .map(|(u, _)| u)?;
+        let path = self.cycle(&scc, start);
+        Some(self.replay(start, &path, cfg))
+    }
+
+    fn edges_within<'a>(
+        &'a self,
+        set: &'a [bool],
+        u: usize,
+    ) -> impl Iterator<Item = (usize, &'a Edge)> + 'a {
+        self.nodes[u]
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.to.is_some_and(|w| set[w]))
+    }
+
+    /// Finds strongly connected component, which contains the fair cycle.
+    fn fair_scc(&self, allowed: Vec<bool>) -> Option<Vec<bool>> {
+        for scc in self.sccs(&allowed) {
+            let nodes: Vec<usize> = (0..scc.len()).filter(|u| scc[*u]).collect();
+            let mut unfair = false;
+            let mut removed = scc.clone();
+            for (i, c) in self.constraints.iter().enumerate() {
+                let taken = nodes
+                    .iter()
+                    .any(|u| self.edges_within(&scc, *u).any(|(_, e)| e.taken[i]));
+                if taken {
+                    continue;
+                }
+                let enabled = nodes.iter().filter(|u| self.nodes[**u].enabled[i]);
+                if c.strong {
+                    // the cycle must avoid the states, in which the step is available
+                    enabled.for_each(|u| removed[*u] = false);
+                } else if enabled.count() == nodes.len() {
+                    unfair = true;
+                }
+            }
+            if unfair {
+                continue;
+            }
+            if removed == scc {
+                return Some(scc);
+            }
+            if let Some(scc) = self.fair_scc(removed) {
+                return Some(scc);
+            }
+        }
+        None
+    }
+
+    /// Returns non-trivial strongly connected components of the allowed states.
+    fn sccs(&self, allowed: &[bool]) -> Vec<Vec<bool>> {
+        let n = self.nodes.len();
+        let mut index: Vec<Option<usize>> = vec![None; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut counter = 0;
+        let mut result = Vec::new();
+
+        for root in (0..n).filter(|u| allowed[*u]) {
+            if index[root].is_some() {
+                continue;
+            }
+            let mut calls = vec![(root, 0)];
+            index[root] = Some(counter);
+            low[root] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some((u, next)) = calls.last_mut() {
+                let u = *u;
+                if let Some(edge) = self.nodes[u].edges.get(*next) {
+                    *next += 1;
+                    let Some(w) = edge.to.filter(|w| allowed[*w]) else {
+                        continue;
+                    };
+                    match index[w] {
+                        None => {
+                            index[w] = Some(counter);
+                            low[w] = counter;
+                            counter += 1;
+                            stack.push(w);
+                            on_stack[w] = true;
+                            calls.push((w, 0));
+                        }
+                        Some(i) if on_stack[w] => low[u] = low[u].min(i),
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+                calls.pop();
+                if let Some((p, _)) = calls.last() {
+                    low[*p] = low[*p].min(low[u]);
+                }
+                if Some(low[u]) == index[u] {
+                    let mut scc = vec![false; n];
+                    let mut size = 0;
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        scc[w] = true;
+                        size += 1;
+                        if w == u {
+                            break;
+                        }
+                    }
+                    let self_loop = self.nodes[u].edges.iter().any(|e| e.to == Some(u));
+                    if size > 1 || self_loop {
+                        result.push(scc);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns the cycle from the start state through the component,
+    /// which takes the steps required by the constraints.
+    fn cycle(&self, scc: &[bool], start: usize) -> Vec<EdgeId> {
+        let mut waypoints = Vec::new();
+        for (i, c) in self.constraints.iter().enumerate() {
+            let taken = (0..scc.len()).filter(|u| scc[*u]).find_map(|u| {
+                self.edges_within(scc, u)
+                    .find(|(_, edge)| edge.taken[i])
+                    .map(|(e, _)| (u, e))
+            });
+            if let Some((u, e)) = taken {
+                waypoints.push(Waypoint::Edge(u, e));
+            } else if !c.strong {
+                let disabled = (0..scc.len()).find(|u| scc[*u] && !self.nodes[*u].enabled[i]);
+                waypoints.extend(disabled.map(Waypoint::Node));
+            }
+        }
+        if waypoints.is_empty() {
+            let (e, _) = self.edges_within(scc, start).next().unwrap();
+            waypoints.push(Waypoint::Edge(start, e));
+        }
+
+        let mut path = Vec::new();
+        let mut current = start;
+        for waypoint in waypoints {
+            match waypoint {
+                Waypoint::Edge(u, e) => {
+                    path.extend(self.path(scc, current, u));
+                    path.push((u, e));
+                    current = self.nodes[u].edges[e].to.unwrap();
+                }
+                Waypoint::Node(u) => {
+                    path.extend(self.path(scc, current, u));
+                    current = u;
+                }
+            }
+        }
+        path.extend(self.path(scc, current, start));
+        path
+    }
+
+    /// Returns the shortest path between the states of the component.
+    fn path(&self, scc: &[bool], from: usize, to: usize) -> Vec<EdgeId> {
+        let mut prev: HashMap<usize, EdgeId> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(u) = queue.pop_front() {
+            if u == to {
+                break;
+            }
+            for (e, edge) in self.edges_within(scc, u) {
+                let w = edge.to.unwrap();
+                if w != from && !prev.contains_key(&w) {
+                    prev.insert(w, (u, e));
+                    queue.push_back(w);
+                }
+            }
+        }
+        let mut path = Vec::new();
+        let mut current = to;
+        while current != from {
+            let (u, e) = prev[&current];
+            path.push((u, e));
+            current = u;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Makes the lasso by replaying the cycle after the trace of the start state.
+    ///
+    /// The states of the cycle are recorded with the steps explored on their first visit,
+    /// so every step is matched with the available step of the same kind,
+    /// which leads to the state with the same hash.
+    /// Panics if the step can not be matched, because the explored system is not deterministic.
+    fn replay(&self, start: usize, path: &[EdgeId], cfg: &SearchConfig) -> FairCycle {
+        let prefix = self.nodes[start].trace.clone();
+        let mut trace = prefix.clone();
+        let mut cycle = StateTrace::new();
+        for (u, e) in path.iter().copied() {
+            let edge = &self.nodes[u].edges[e];
+            let hash = self.nodes[edge.to.unwrap()].system_hash;
+            let step = align(&trace, &edge.step, hash, cfg).unwrap_or_else(|| {
+                panic!(
+                    "step {} of the cycle does not lead to the explored state, \
+                     the system is not deterministic",
+                    cycle.depth()
+                )
+            });
+            trace.add_step(step.clone());
+            cycle.add_step(step);
+        }
+        let log = SearchState::from_trace(&trace)
+            .expect("aligned steps are made without panics")
+            .system
+            .handle()
+            .log();
+        FairCycle::new(prefix, cycle, log, self.nodes[start].system_hash)
+    }
+}
+
+fn align(
+    trace: &StateTrace,
+    step: &StateTraceStep,
+    hash: HashType,
+    cfg: &SearchConfig,
+) -> Option<StateTraceStep> {
+    let state = SearchState::from_trace(trace).ok()?;
+    state
+        .steps(cfg)
+        .into_iter()
+        .filter(|s| discriminant(s) == discriminant(step.inner()))
+        .map(|s| match step {
+            StateTraceStep::Choose(_, choices) => {
+                StateTraceStep::Choose(Box::new(s), choices.clone())
+            }
+            _ => s,
+        })
+        .find(|s| {
+            let mut next = trace.clone();
+            next.add_step(s.clone());
+            SearchState::from_trace(&next).is_ok_and(|s| s.system.handle().hash() == hash)
+        })
+}
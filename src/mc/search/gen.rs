@@ -1,6 +1,6 @@
 use std::{
-    collections::HashMap,
-    hash::{DefaultHasher, Hasher},
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    hash::Hash,
     time::Duration,
 };
 
@@ -12,8 +12,10 @@ use crate::{
             info::{EventInfo, RpcEventKind, RpcMessageKind, TcpEventKind},
             Event,
         },
-        SystemHandle,
+        HashType, SystemHandle,
     },
+    util::hash::{fingerprint, Hasher128},
+    Address, FsError,
 };
 
 use super::{
@@ -38,10 +40,54 @@ enum EventKind {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Processes, between which the event happens.
+#[derive(Clone)]
+pub struct EventRoute {
+    pub from: Option<Address>,
+    pub to: Address,
+    // tag of the rpc request
+    pub tag: Option<u64>,
+}
+
+impl EventRoute {
+    pub fn message(from: Address, to: Address) -> Self {
+        Self {
+            from: Some(from),
+            to,
+            tag: None,
+        }
+    }
+
+    pub fn to(to: Address) -> Self {
+        Self {
+            from: None,
+            to,
+            tag: None,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 pub struct Generator {
     tracker: Option<MooreEventTracker<i64>>,
     event_info: HashMap<usize, EventKind>,
+    event_route: HashMap<usize, EventRoute>,
     last_selected: Option<usize>,
+    choices: Choices,
+    // number of the random seeds taken by every process
+    random_seeds: BTreeMap<Address, u64>,
+    // ids of the sent workload messages
+    sent: BTreeSet<usize>,
+}
+
+/// Nondeterministic choices made by the processes during the last applied step.
+#[derive(Default)]
+struct Choices {
+    forced: VecDeque<usize>,
+    forced_count: usize,
+    // pairs of the chosen value and the number of options
+    made: Vec<(usize, usize)>,
 }
 
 impl Generator {
@@ -51,10 +97,28 @@ impl Generator {
 }
 
 impl EventDriver for Generator {
-    fn hash_pending(&self) -> u64 {
-        let mut hasher = DefaultHasher::new();
+    fn hash_pending(&self) -> HashType {
+        let mut hasher = Hasher128::new();
         self.tracker.as_ref().unwrap().hash_pending(&mut hasher);
-        hasher.finish()
+        self.sent.hash(&mut hasher);
+        self.random_seeds.hash(&mut hasher);
+        hasher.finish128()
+    }
+
+    fn choose(&mut self, n: usize) -> usize {
+        // not forced choices are resolved to the first option
+        let value = self.choices.forced.pop_front().unwrap_or(0);
+        assert!(value < n, "can not choose {} of {}", value, n);
+        self.choices.made.push((value, n));
+        value
+    }
+
+    fn random_seed(&mut self, proc: &Address) -> u64 {
+        // random values are fixed by the process and the number of its previous calls,
+        // so they do not depend on the order of the steps of the other processes
+        let calls = self.random_seeds.entry(proc.clone()).or_default();
+        *calls += 1;
+        fingerprint(&(proc, *calls)) as u64
     }
 
     fn register_event(&mut self, event: &Event, min_delay: Duration, max_delay: Duration) {
@@ -90,12 +154,30 @@ impl EventDriver for Generator {
             };
         let prev_value = self.event_info.insert(event.id, kind);
         assert!(prev_value.is_none());
+
+        let route = match &event.info {
+            EventInfo::UdpMessage(msg) => EventRoute::message(msg.from.address(), msg.to.address()),
+            EventInfo::Timer(timer) => EventRoute::to(timer.proc.address()),
+            EventInfo::TcpMessage(msg) => EventRoute::message(msg.from.address(), msg.to.address()),
+            EventInfo::TcpEvent(e) => EventRoute::to(e.to.address()),
+            EventInfo::FsEvent(e) => EventRoute::to(e.proc.clone()),
+            EventInfo::RpcMessage(msg) => {
+                let mut route = EventRoute::message(msg.from.address(), msg.to.address());
+                if let RpcMessageKind::Request { tag, .. } = &msg.kind {
+                    route.tag = Some(*tag);
+                }
+                route
+            }
+            EventInfo::RpcEvent(e) => EventRoute::to(e.to.address()),
+        };
+        self.event_route.insert(event.id, route);
     }
 
     fn cancel_event(&mut self, event: &Event) {
         self.tracker.as_mut().unwrap().cancel_event(event.id + 1);
         let removed = self.event_info.remove(&event.id);
         assert!(removed.is_some());
+        self.event_route.remove(&event.id);
     }
 }
 
@@ -104,7 +186,11 @@ impl Default for Generator {
         Self {
             tracker: Some(Default::default()),
             event_info: Default::default(),
+            event_route: Default::default(),
             last_selected: Default::default(),
+            choices: Default::default(),
+            random_seeds: Default::default(),
+            sent: Default::default(),
         }
     }
 }
@@ -120,10 +206,42 @@ impl Generator {
         self.last_selected = Some(id);
     }
 
+    /// Force the choices made during the next applied step.
+    pub fn force_choices(&mut self, forced: &[usize]) {
+        self.choices = Choices {
+            forced: forced.iter().copied().collect(),
+            forced_count: forced.len(),
+            made: Vec::new(),
+        };
+    }
+
+    /// Returns choices made during the last applied step,
+    /// and the number of the forced ones among them.
+    pub fn choices(&self) -> (&[(usize, usize)], usize) {
+        (&self.choices.made, self.choices.forced_count)
+    }
+
+    /// Mark the workload message as sent.
+    pub fn mark_sent(&mut self, id: usize) {
+        self.sent.insert(id);
+    }
+
+    /// Returns address of the process, which handles the event.
+    pub fn event_target(&self, event_id: usize) -> Option<&Address> {
+        self.event_route.get(&event_id).map(|r| &r.to)
+    }
+
+    /// Returns source and target of the event.
+    pub fn event_route(&self, event_id: usize) -> Option<&EventRoute> {
+        self.event_route.get(&event_id)
+    }
+
     pub fn steps(&self, system: SystemHandle, cfg: &SearchConfig) -> Vec<StateTraceStep> {
         let mut res = Vec::new();
         let mut tcp_filter = ReadyTcpPacketFilter::new();
         let mut rpc_filter = ReadyRpcRequestsFilter::new();
+        let disk_fault_allowed =
+            system.stat().disk_faults < cfg.max_disk_faults.unwrap_or(0);
         for (e, _) in self.tracker.as_ref().unwrap().next_events() {
             let time = self.tracker.as_ref().unwrap().event_time(e);
             assert!(time >= 0);
@@ -214,6 +332,19 @@ impl Generator {
                         },
                     );
                     res.push(step);
+
+                    // inject io failure
+                    if kind.outcome.is_ok() && disk_fault_allowed {
+                        let step = StateTraceStep::FailFsEvent(
+                            event_id,
+                            FsEvent {
+                                event_id,
+                                time,
+                                outcome: Err(FsError::IoFailure),
+                            },
+                        );
+                        res.push(step);
+                    }
                 }
             }
         }
@@ -245,6 +376,38 @@ impl Generator {
             }
         }
 
+        if system.stat().nodes_restarted < cfg.max_node_restarts.unwrap_or(0) {
+            for i in 0..system.nodes_count() {
+                if system.node_restartable_index(i) {
+                    res.push(StateTraceStep::RestartNode(i));
+                }
+            }
+        }
+
+        if disk_fault_allowed {
+            for i in 0..system.nodes_count() {
+                if system.fs_available_index(i) {
+                    res.push(StateTraceStep::CrashFs(i));
+                }
+            }
+        }
+
+        if self.sent.len() < cfg.max_local_messages.unwrap_or(usize::MAX) {
+            for msg in cfg.workload.ready(&self.sent) {
+                if system.proc(msg.to()).is_some() {
+                    res.push(StateTraceStep::SendLocal(msg));
+                }
+            }
+        }
+
+        if system.network().partitioned() {
+            res.push(StateTraceStep::Heal);
+        } else if system.stat().network_partitions < cfg.max_partitions.unwrap_or(0) {
+            for nodes in system.partitions_index() {
+                res.push(StateTraceStep::Partition(nodes));
+            }
+        }
+
         res
     }
 }
@@ -0,0 +1,236 @@
+//! Recording of the state graph explored by [`crate::mc::BfsSearcher`]
+//! and [`crate::mc::DfsSearcher`], which can be exported
+//! in the Graphviz DOT or JSON format for the offline inspection.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+
+use crate::model::system::HashType;
+
+use super::state::StateTrace;
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Mark of the explored state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeMark {
+    /// State from which the search started.
+    Initial,
+
+    /// State without available transitions.
+    Terminal,
+
+    /// State pruned by the prune function.
+    Pruned,
+
+    /// State in which the goal is achieved.
+    Goal,
+
+    /// State in which the invariant or the temporal property is violated.
+    Violation,
+}
+
+impl NodeMark {
+    fn dot_attrs(&self) -> &'static str {
+        match self {
+            NodeMark::Initial => "shape=box",
+            NodeMark::Terminal => "peripheries=2",
+            NodeMark::Pruned => "style=dashed, color=gray",
+            NodeMark::Goal => "color=green",
+            NodeMark::Violation => "color=red",
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Default)]
+struct StateGraph {
+    nodes: BTreeMap<HashType, BTreeSet<NodeMark>>,
+    edges: BTreeSet<(HashType, HashType, String)>,
+}
+
+#[derive(Serialize)]
+struct NodeRecord {
+    hash: String,
+    marks: Vec<NodeMark>,
+}
+
+#[derive(Serialize)]
+struct EdgeRecord {
+    from: String,
+    to: String,
+    step: String,
+}
+
+#[derive(Serialize, Default)]
+struct GraphRecord {
+    nodes: Vec<NodeRecord>,
+    edges: Vec<EdgeRecord>,
+}
+
+fn node_id(h: HashType) -> String {
+    format!("{:032x}", h)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Records the states explored by the searcher as nodes identified by the state hash,
+/// and the steps between them as edges labelled with the step description
+/// (see [`crate::mc::BfsSearcher::record_graph`] and [`crate::mc::DfsSearcher::record_graph`]).
+///
+/// Clones of the recorder share the same graph,
+/// so the graph can be exported after the searcher is consumed by the checker.
+#[derive(Clone)]
+pub struct GraphRecorder {
+    graph: Option<Arc<Mutex<StateGraph>>>,
+}
+
+impl Default for GraphRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphRecorder {
+    /// Create new recorder with empty graph.
+    pub fn new() -> Self {
+        Self {
+            graph: Some(Default::default()),
+        }
+    }
+
+    /// Recorder which records nothing.
+    pub(crate) fn disabled() -> Self {
+        Self { graph: None }
+    }
+
+    fn with<R: Default>(&self, f: impl FnOnce(&mut StateGraph) -> R) -> R {
+        self.graph
+            .as_ref()
+            .map(|g| f(&mut g.lock().unwrap()))
+            .unwrap_or_default()
+    }
+
+    /// Record state with the provided hash, reached from the parent state
+    /// by the last step of the trace.
+    pub(crate) fn visit(&self, parent: Option<HashType>, trace: &StateTrace, h: HashType) {
+        self.with(|g| {
+            let marks = g.nodes.entry(h).or_default();
+            match (parent, trace.steps().last()) {
+                (Some(parent), Some(step)) => {
+                    g.edges.insert((parent, h, step.to_string()));
+                }
+                _ => {
+                    marks.insert(NodeMark::Initial);
+                }
+            }
+        })
+    }
+
+    /// Mark the recorded state.
+    pub(crate) fn mark(&self, h: HashType, mark: NodeMark) {
+        self.with(|g| g.nodes.entry(h).or_default().insert(mark));
+    }
+
+    /// Returns number of the recorded states.
+    pub fn nodes_count(&self) -> usize {
+        self.with(|g| g.nodes.len())
+    }
+
+    /// Returns number of the recorded transitions.
+    pub fn edges_count(&self) -> usize {
+        self.with(|g| g.edges.len())
+    }
+
+    /// Returns hashes of the recorded states with the provided mark.
+    pub fn marked(&self, mark: NodeMark) -> Vec<HashType> {
+        self.with(|g| {
+            g.nodes
+                .iter()
+                .filter(|(_, marks)| marks.contains(&mark))
+                .map(|(h, _)| *h)
+                .collect()
+        })
+    }
+
+    /// Export the recorded graph in the Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        self.with(|g| {
+            let mut dot = String::from("digraph states {\n");
+            for (h, marks) in g.nodes.iter() {
+                let mut label = node_id(*h);
+                let mut attrs = String::new();
+                for mark in marks {
+                    write!(label, "\\n{:?}", mark).unwrap();
+                    write!(attrs, ", {}", mark.dot_attrs()).unwrap();
+                }
+                writeln!(
+                    dot,
+                    "    \"{}\" [label=\"{}\"{}];",
+                    node_id(*h),
+                    label,
+                    attrs
+                )
+                .unwrap();
+            }
+            for (from, to, step) in g.edges.iter() {
+                writeln!(
+                    dot,
+                    "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                    node_id(*from),
+                    node_id(*to),
+                    escape(step)
+                )
+                .unwrap();
+            }
+            dot.push_str("}\n");
+            dot
+        })
+    }
+
+    /// Export the recorded graph in the JSON format.
+    pub fn to_json(&self) -> String {
+        let record = self.with(|g| GraphRecord {
+            nodes: g
+                .nodes
+                .iter()
+                .map(|(h, marks)| NodeRecord {
+                    hash: node_id(*h),
+                    marks: marks.iter().copied().collect(),
+                })
+                .collect(),
+            edges: g
+                .edges
+                .iter()
+                .map(|(from, to, step)| EdgeRecord {
+                    from: node_id(*from),
+                    to: node_id(*to),
+                    step: step.clone(),
+                })
+                .collect(),
+        });
+        serde_json::to_string_pretty(&record).expect("graph is always serializable")
+    }
+
+    /// Save the recorded graph in the Graphviz DOT format into the file.
+    pub fn save_dot(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_dot())
+    }
+
+    /// Save the recorded graph in the JSON format into the file.
+    pub fn save_json(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+}
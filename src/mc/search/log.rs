@@ -1,4 +1,11 @@
-use std::fmt::{Debug, Display};
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Display},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use super::{config::SearchConfig, state::StateTrace, step::StateTraceStep};
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -12,21 +19,107 @@ pub struct SearchLog {
     /// Total number of uniques states with different hash,
     /// visited during the search.
     pub visited_unique: usize,
+
+    /// Max depth of the visited states.
+    pub max_depth: usize,
+
+    /// Number of the pruned states.
+    pub pruned: usize,
+
+    /// Number of the branched states.
+    pub branched: usize,
+
+    /// Number of the explored steps by the kind of step.
+    pub steps: BTreeMap<&'static str, usize>,
+
+    /// Number of the visited states by the kind of fault injected by the last step.
+    pub faults: BTreeMap<&'static str, usize>,
+
+    /// Number of the steps applied to make the visited states.
+    /// States resumed from the snapshots apply only the steps made after the snapshot
+    /// (see [`SearchConfig::max_snapshots`](crate::mc::SearchConfig::max_snapshots)).
+    pub replayed: usize,
+
+    /// Number of the states waiting to be visited, sampled over time
+    /// (see [`SearchConfigBuilder::progress`](crate::mc::SearchConfigBuilder::progress)).
+    pub frontier: Vec<(Duration, usize)>,
+
+    /// Duration of the search.
+    pub elapsed: Duration,
 }
 
 impl SearchLog {
     pub(crate) fn new() -> Self {
         Default::default()
     }
+
+    pub(crate) fn visit(&mut self, trace: &StateTrace) {
+        self.visited_total += 1;
+        self.max_depth = self.max_depth.max(trace.depth());
+        if let Some(fault) = trace.steps().last().and_then(|s| s.fault()) {
+            *self.faults.entry(fault).or_default() += 1;
+        }
+    }
+
+    pub(crate) fn replay(&mut self, steps: usize) {
+        self.replayed += steps;
+    }
+
+    pub(crate) fn branch<'a>(&mut self, steps: impl IntoIterator<Item = &'a StateTraceStep>) {
+        self.branched += 1;
+        for step in steps {
+            *self.steps.entry(step.kind()).or_default() += 1;
+        }
+    }
+
+    pub(crate) fn choose(&mut self, traces: &[StateTrace]) {
+        for step in traces.iter().filter_map(|t| t.steps().last()) {
+            *self.steps.entry(step.kind()).or_default() += 1;
+        }
+    }
+
+    /// Returns average number of the explored steps of provided kind per branched state.
+    pub fn branching_factor(&self, kind: &str) -> f64 {
+        let steps = self.steps.get(kind).copied().unwrap_or(0);
+        steps as f64 / self.branched.max(1) as f64
+    }
+
+    /// Returns number of the visited states per second.
+    pub fn states_per_second(&self) -> f64 {
+        self.visited_total as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Returns estimated probability that some of the unique visited states
+    /// have the same 128-bit hash, so the search could miss them.
+    ///
+    /// Estimate is made by the birthday bound and assumes the uniform hashing.
+    pub fn collision_probability(&self) -> f64 {
+        let n = self.visited_unique as f64;
+        (n * (n - 1.).max(0.) / 2f64.powi(129)).min(1.)
+    }
 }
 
 impl Display for SearchLog {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
+        writeln!(
             f,
             "Unique visited: {}, total visited: {}",
             self.visited_unique, self.visited_total
-        )
+        )?;
+        write!(f, "Max depth: {}, pruned: {}", self.max_depth, self.pruned)?;
+        if !self.steps.is_empty() {
+            write!(f, "\nExplored steps:")?;
+            for (kind, count) in self.steps.iter() {
+                write!(f, " {}={}", kind, count)?;
+            }
+        }
+        if !self.faults.is_empty() {
+            write!(f, "\nInjected faults:")?;
+            for (kind, count) in self.faults.iter() {
+                write!(f, " {}={}", kind, count)?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -35,3 +128,55 @@ impl Debug for SearchLog {
         write!(f, "{}", self)
     }
 }
+
+////////////////////////////////////////////////////////////////////////////////
+
+type ReportFn = Arc<dyn Fn(&SearchLog) + Send + Sync>;
+
+/// Reports progress of the long search (see [`crate::mc::SearchConfigBuilder::progress`]).
+#[derive(Clone)]
+pub struct Progress {
+    interval: Duration,
+    report: Option<ReportFn>,
+}
+
+impl Progress {
+    pub(crate) fn new(interval: Duration, report: Option<ReportFn>) -> Self {
+        Self { interval, report }
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(1), None)
+    }
+}
+
+/// Measures the search time, samples the frontier and reports progress.
+pub(crate) struct SearchClock {
+    start: Instant,
+    next_sample: Duration,
+    progress: Progress,
+}
+
+impl SearchClock {
+    pub fn new(cfg: &SearchConfig) -> Self {
+        Self {
+            start: Instant::now(),
+            next_sample: Duration::ZERO,
+            progress: cfg.progress.clone(),
+        }
+    }
+
+    pub fn tick(&mut self, log: &mut SearchLog, frontier: usize) {
+        log.elapsed = self.start.elapsed();
+        if log.elapsed < self.next_sample {
+            return;
+        }
+        log.frontier.push((log.elapsed, frontier));
+        self.next_sample = log.elapsed + self.progress.interval;
+        if let Some(report) = self.progress.report.as_ref() {
+            report(log);
+        }
+    }
+}
@@ -1,16 +1,32 @@
+pub mod best_first;
 pub mod bfs;
+mod budget;
 pub mod config;
 pub mod control;
 pub mod dfs;
+mod entry;
 pub mod error;
+pub mod fairness;
 mod fs;
 mod gen;
+pub mod graph;
 pub mod log;
+pub mod parallel;
+mod por;
+pub mod property;
+pub mod random;
 mod rpc;
+pub mod schedule;
 pub mod searcher;
+pub mod shrink;
 pub mod state;
 pub mod step;
 mod tcp;
+pub(crate) mod trace;
+pub mod transition;
+mod violations;
+pub mod visited;
+pub mod workload;
 // mod tracker;
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,481 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use super::{
+    config::SearchConfig,
+    control::{AbstractionFn, GoalFn, InvariantFn, PruneFn},
+    error::{
+        AllPruned, Cycled, InvariantViolation, LivenessViolation, SearchError, SearchErrorKind,
+    },
+    log::{SearchClock, SearchLog},
+    por::{SleepCache, SleepSet, Visit},
+    searcher::{CollectInfo, Searcher},
+    state::{SearchState, StateTrace},
+    step::StateTraceStep,
+    transition::Transitions,
+    visited::VisitedSet,
+};
+
+use crate::{
+    mc::StateView,
+    model::{log::Log, system::HashType},
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Represents multi-threaded searcher.
+///
+/// Explores states level by level in the same order as [`crate::mc::BfsSearcher`].
+/// States of the level are rebuilt from their traces and checked
+/// by the worker threads, after which the results are merged
+/// in the order of the level, so the explored states do not depend
+/// on the number of workers.
+///
+/// Workers share the visited set and do not expand the states,
+/// which are visited on the previous levels or earlier on the same level.
+/// Every state is replayed from its trace, the previous state of the transition
+/// invariants (see [`crate::mc::ModelChecker::transition_invariant`]) is replayed too.
+pub struct ParallelSearcher {
+    cfg: SearchConfig,
+    workers: usize,
+}
+
+impl ParallelSearcher {
+    /// Make new searcher with provided config and number of worker threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of workers is zero or the config contains the settings,
+    /// which are not supported by the searcher: temporal properties, fairness constraints,
+    /// keep going mode, search budgets and snapshots.
+    pub fn new(cfg: SearchConfig, workers: usize) -> Self {
+        assert!(workers > 0, "number of workers must be positive");
+        assert!(
+            cfg.properties.is_empty(),
+            "temporal properties are not checked by parallel search"
+        );
+        assert!(
+            cfg.fairness.is_empty(),
+            "fairness constraints are not checked by parallel search"
+        );
+        assert!(
+            cfg.max_violations.is_none(),
+            "keep going mode is not supported by parallel search"
+        );
+        assert!(
+            cfg.max_depth.is_none() && cfg.max_visited_states.is_none() && cfg.time_limit.is_none(),
+            "search budgets are not supported by parallel search"
+        );
+        assert!(
+            cfg.max_snapshots.is_none(),
+            "snapshots are not supported by parallel search"
+        );
+        Self { cfg, workers }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Check,
+    Collect,
+}
+
+/// Result of the state check made by worker.
+enum Verdict {
+    Panic(SearchErrorKind),
+    InvariantViolation { report: String, log: Log },
+    GoalAchieved,
+    Pruned,
+    Terminal { report: String, log: Log },
+    Visited,
+    Branch(Vec<(StateTraceStep, SleepSet)>),
+}
+
+/// Visited set shared by the workers during the evaluation of the level.
+struct SharedVisited<'a> {
+    // states visited on the previous levels
+    visited: Mutex<&'a mut dyn VisitedSet>,
+    // least index of the level state with empty sleep set by hash
+    claimed: Mutex<HashMap<HashType, usize>>,
+}
+
+impl<'a> SharedVisited<'a> {
+    fn new(visited: &'a mut dyn VisitedSet) -> Self {
+        Self {
+            visited: Mutex::new(visited),
+            claimed: Default::default(),
+        }
+    }
+
+    /// Returns if the state of the level with provided index is explored
+    /// by the state visited before it, so it will be marked as explored after the merge.
+    fn explored(&self, index: usize, hash: HashType, sleep: &SleepSet) -> bool {
+        if self.visited.lock().unwrap().contains(hash) {
+            return true;
+        }
+        let mut claimed = self.claimed.lock().unwrap();
+        if sleep.is_empty() {
+            let least = claimed.entry(hash).or_insert(index);
+            *least = (*least).min(index);
+            *least < index
+        } else {
+            claimed.get(&hash).is_some_and(|least| *least < index)
+        }
+    }
+}
+
+struct Evaluated {
+    hash: HashType,
+    verdict: Verdict,
+    // trace with the recorded choices and traces with the other choices
+    trace: StateTrace,
+    choices: Vec<StateTrace>,
+}
+
+impl ParallelSearcher {
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate(
+        &self,
+        index: usize,
+        (trace, sleep): &(StateTrace, SleepSet),
+        shared: &SharedVisited,
+        mode: Mode,
+        invariant: &impl InvariantFn,
+        transitions: &Transitions,
+        prune: &impl PruneFn,
+        goal: &impl GoalFn,
+        abstraction: &impl AbstractionFn,
+    ) -> Evaluated {
+        let state = match SearchState::from_trace(trace) {
+            Ok(state) => state,
+            Err(kind) => {
+                return Evaluated {
+                    hash: 0,
+                    verdict: Verdict::Panic(kind),
+                    trace: trace.clone(),
+                    choices: Vec::new(),
+                }
+            }
+        };
+        let mut trace = trace.clone();
+        let choices = state.branch_choices(&mut trace);
+        let system = state.system.handle();
+        let view = StateView::new(&state, trace.clone());
+        let hash = abstraction(view.clone());
+
+        // the previous state of the transition is replayed by the worker
+        let transition = transitions.check(None, &view);
+        let verdict = if let Err(report) = invariant(view.clone()).and(transition) {
+            Verdict::InvariantViolation {
+                report,
+                log: system.log(),
+            }
+        } else {
+            match mode {
+                Mode::Check => match goal(view.clone()) {
+                    Ok(()) => Verdict::GoalAchieved,
+                    Err(_) if prune(view) => Verdict::Pruned,
+                    Err(_) if shared.explored(index, hash, sleep) => Verdict::Visited,
+                    Err(report) => {
+                        let steps = state.gen.borrow().steps(system.clone(), &self.cfg);
+                        if steps.is_empty() {
+                            Verdict::Terminal {
+                                report,
+                                log: system.log(),
+                            }
+                        } else {
+                            Verdict::Branch(sleep.branch(&self.cfg, &state.gen.borrow(), steps))
+                        }
+                    }
+                },
+                Mode::Collect => {
+                    if prune(view.clone()) {
+                        Verdict::Pruned
+                    } else if shared.explored(index, hash, sleep) {
+                        Verdict::Visited
+                    } else if goal(view).is_ok() {
+                        Verdict::GoalAchieved
+                    } else {
+                        let steps = state.gen.borrow().steps(system, &self.cfg);
+                        Verdict::Branch(sleep.branch(&self.cfg, &state.gen.borrow(), steps))
+                    }
+                }
+            }
+        };
+
+        Evaluated {
+            hash,
+            verdict,
+            trace,
+            choices,
+        }
+    }
+
+    /// Evaluates states of the level using worker threads.
+    /// Returned results are in the order of the level.
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate_level(
+        &self,
+        level: &[(StateTrace, SleepSet)],
+        visited: &mut dyn VisitedSet,
+        mode: Mode,
+        invariant: &impl InvariantFn,
+        transitions: &Transitions,
+        prune: &impl PruneFn,
+        goal: &impl GoalFn,
+        abstraction: &impl AbstractionFn,
+    ) -> Vec<Evaluated> {
+        let shared = SharedVisited::new(visited);
+        let next = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<Evaluated>>> =
+            Mutex::new((0..level.len()).map(|_| None).collect());
+        std::thread::scope(|s| {
+            for _ in 0..self.workers.min(level.len()) {
+                s.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= level.len() {
+                        break;
+                    }
+                    let result = self.evaluate(
+                        i,
+                        &level[i],
+                        &shared,
+                        mode,
+                        invariant,
+                        transitions,
+                        prune,
+                        goal,
+                        abstraction,
+                    );
+                    results.lock().unwrap()[i] = Some(result);
+                });
+            }
+        });
+        results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|r| r.unwrap())
+            .collect()
+    }
+}
+
+/// Replays the trace to get log of the state.
+/// Trace was successfully replayed before, so it can not fail.
+fn trace_log(trace: &StateTrace) -> Log {
+    SearchState::from_trace(trace)
+        .expect("trace was replayed before")
+        .system
+        .handle()
+        .log()
+}
+
+fn branch(
+    v: &StateTrace,
+    steps: Vec<(StateTraceStep, SleepSet)>,
+    next: &mut Vec<(StateTrace, SleepSet)>,
+    log: &mut SearchLog,
+) {
+    log.branch(steps.iter().map(|(s, _)| s));
+    steps
+        .into_iter()
+        .map(|(s, sleep)| {
+            let mut u = v.clone();
+            u.add_step(s);
+            (u, sleep)
+        })
+        .for_each(|u| next.push(u));
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl Searcher for ParallelSearcher {
+    fn check(
+        &mut self,
+        start: Vec<StateTrace>,
+        visited: &mut dyn VisitedSet,
+        invariant: impl InvariantFn,
+        transitions: &Transitions,
+        prune: impl PruneFn,
+        goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
+    ) -> Result<SearchLog, SearchError> {
+        let mut log = SearchLog::new();
+        let mut sleeping = SleepCache::default();
+        let mut clock = SearchClock::new(&self.cfg);
+
+        let mut level: Vec<_> = start
+            .into_iter()
+            .map(|v| (v, SleepSet::default()))
+            .collect();
+
+        let mut last_prune = None;
+        let mut last_already_meet = None;
+
+        let mut goal_achieved = false;
+        while !level.is_empty() {
+            let results = self.evaluate_level(
+                &level,
+                visited,
+                Mode::Check,
+                &invariant,
+                transitions,
+                &prune,
+                &goal,
+                &abstraction,
+            );
+            let mut next = Vec::new();
+            let mut waiting = level.len();
+            for ((v, sleep), e) in level.into_iter().zip(results) {
+                waiting -= 1;
+                log.visit(&v);
+                log.replay(v.depth());
+                clock.tick(&mut log, waiting + next.len());
+                if let Verdict::Panic(kind) = e.verdict {
+                    return Err(SearchError::new(kind, &log));
+                }
+                let v = e.trace;
+                log.choose(&e.choices);
+                next.extend(e.choices.into_iter().map(|u| (u, sleep.clone())));
+                let h = e.hash;
+                let visit = sleep.visit(visited, &mut sleeping, h);
+                if visit == Visit::New {
+                    log.visited_unique += 1;
+                }
+                let already_meet = visit == Visit::Explored;
+
+                match e.verdict {
+                    Verdict::Panic(_) => unreachable!(),
+                    Verdict::InvariantViolation { report, log: l } => {
+                        let err = InvariantViolation {
+                            trace: v,
+                            log: l,
+                            report,
+                        };
+                        let kind = SearchErrorKind::InvariantViolation(err);
+                        return Err(SearchError::new(kind, &log));
+                    }
+                    Verdict::GoalAchieved => goal_achieved = true,
+                    Verdict::Pruned => {
+                        log.pruned += 1;
+                        last_prune = Some(v);
+                    }
+                    Verdict::Terminal { report, log: l } => {
+                        let err = LivenessViolation::new(v, l, report);
+                        let err = SearchErrorKind::LivenessViolation(err);
+                        return Err(SearchError::new(err, &log));
+                    }
+                    Verdict::Visited => {
+                        debug_assert!(already_meet);
+                        last_already_meet = Some((v, h));
+                    }
+                    Verdict::Branch(steps) => {
+                        if already_meet {
+                            last_already_meet = Some((v, h));
+                        } else {
+                            branch(&v, steps, &mut next, &mut log);
+                        }
+                    }
+                }
+            }
+            level = next;
+        }
+
+        if goal_achieved {
+            Ok(log)
+        } else if let Some(last_prune) = last_prune {
+            let log_of_state = trace_log(&last_prune);
+            let err = SearchErrorKind::AllPruned(AllPruned::new(last_prune, log_of_state));
+            let err = SearchError::new(err, &log);
+            Err(err)
+        } else {
+            let (trace, h) = last_already_meet.unwrap();
+            let log_of_state = trace_log(&trace);
+            let err = SearchErrorKind::Cycled(Cycled::new(trace, log_of_state, h));
+            let err = SearchError::new(err, &log);
+            Err(err)
+        }
+    }
+
+    fn collect(
+        &mut self,
+        start: Vec<StateTrace>,
+        visited: &mut dyn VisitedSet,
+        invariant: impl InvariantFn,
+        transitions: &Transitions,
+        prune: impl PruneFn,
+        goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
+    ) -> Result<CollectInfo, SearchError> {
+        let mut log = SearchLog::new();
+        let mut sleeping = SleepCache::default();
+        let mut clock = SearchClock::new(&self.cfg);
+        let mut collected = Vec::new();
+        let mut level: Vec<_> = start
+            .into_iter()
+            .map(|v| (v, SleepSet::default()))
+            .collect();
+
+        while !level.is_empty() {
+            let results = self.evaluate_level(
+                &level,
+                visited,
+                Mode::Collect,
+                &invariant,
+                transitions,
+                &prune,
+                &goal,
+                &abstraction,
+            );
+            let mut next = Vec::new();
+            let mut waiting = level.len();
+            for ((v, sleep), e) in level.into_iter().zip(results) {
+                waiting -= 1;
+                log.visit(&v);
+                log.replay(v.depth());
+                clock.tick(&mut log, waiting + next.len());
+                if let Verdict::Panic(kind) = e.verdict {
+                    return Err(SearchError::new(kind, &log));
+                }
+                let v = e.trace;
+                log.choose(&e.choices);
+                next.extend(e.choices.into_iter().map(|u| (u, sleep.clone())));
+                let visit = sleep.visit(visited, &mut sleeping, e.hash);
+                if visit == Visit::New {
+                    log.visited_unique += 1;
+                }
+                let already_meet = visit == Visit::Explored;
+
+                match e.verdict {
+                    Verdict::InvariantViolation { report, log: l } => {
+                        let kind = SearchErrorKind::InvariantViolation(InvariantViolation {
+                            trace: v,
+                            log: l,
+                            report,
+                        });
+                        return Err(SearchError::new(kind, &log));
+                    }
+                    Verdict::Pruned => log.pruned += 1,
+                    Verdict::Visited => debug_assert!(already_meet),
+                    _ if already_meet => {}
+                    Verdict::GoalAchieved => collected.push(v),
+                    Verdict::Branch(steps) => branch(&v, steps, &mut next, &mut log),
+                    _ => {}
+                }
+            }
+            level = next;
+        }
+
+        Ok(CollectInfo {
+            states: collected,
+            log,
+        })
+    }
+}
@@ -0,0 +1,166 @@
+//! Partial order reduction of the search using sleep sets
+//! (see [`SearchConfig::partial_order_reduction`]).
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use crate::{model::system::HashType, Address};
+
+use super::{config::SearchConfig, gen::Generator, step::StateTraceStep, visited::VisitedSet};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Event selection step, which can be delayed without losing any state.
+///
+/// Only the delivery of the UDP messages and the timers can sleep,
+/// because the other steps depend on the state shared by the processes,
+/// such as TCP connections and RPC calls.
+#[derive(Clone)]
+struct SleepingStep {
+    event_id: usize,
+    fault: bool,
+    target: Address,
+}
+
+impl SleepingStep {
+    fn new(step: &StateTraceStep, gen: &Generator) -> Option<Self> {
+        let event_id = step.event_id()?;
+        let fault = match step.inner() {
+            StateTraceStep::SelectUdp(_, msg) => msg.drop,
+            StateTraceStep::SelectTimer(..) => false,
+            _ => return None,
+        };
+        let target = gen.event_target(event_id)?.clone();
+        Some(Self {
+            event_id,
+            fault,
+            target,
+        })
+    }
+
+    fn same(&self, other: &Self) -> bool {
+        self.event_id == other.event_id && self.fault == other.fault
+    }
+
+    /// Faults are dependent with each other, because they share the faults budget.
+    fn independent(&self, other: &Self) -> bool {
+        self.target != other.target && !(self.fault && other.fault)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Steps, which are not explored from the search state,
+/// because the states they lead to are explored from the other states.
+#[derive(Clone, Default)]
+pub(crate) struct SleepSet {
+    steps: Vec<SleepingStep>,
+}
+
+impl SleepSet {
+    /// Returns steps, which must be explored from the state,
+    /// together with the sleep sets of the states they lead to.
+    pub fn branch(
+        &self,
+        cfg: &SearchConfig,
+        gen: &Generator,
+        steps: Vec<StateTraceStep>,
+    ) -> Vec<(StateTraceStep, SleepSet)> {
+        if !cfg.partial_order_reduction {
+            return steps.into_iter().map(|s| (s, Self::default())).collect();
+        }
+
+        let mut explored: Vec<SleepingStep> = Vec::new();
+        let mut result = Vec::new();
+        for step in steps {
+            let Some(sleeping) = SleepingStep::new(&step, gen) else {
+                result.push((step, Self::default()));
+                continue;
+            };
+            if self.steps.iter().any(|s| s.same(&sleeping)) {
+                continue;
+            }
+            let steps = self
+                .steps
+                .iter()
+                .chain(explored.iter())
+                .filter(|s| s.independent(&sleeping))
+                .cloned()
+                .collect();
+            explored.push(sleeping);
+            result.push((step, Self { steps }));
+        }
+        result
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Returns if all steps of the sleep set are in the other one.
+    fn subset_of(&self, other: &Self) -> bool {
+        self.steps
+            .iter()
+            .all(|s| other.steps.iter().any(|o| o.same(s)))
+    }
+
+    /// Marks state as visited with the sleep set and returns the result of the visit.
+    ///
+    /// Not all steps are explored from the state with non-empty sleep set,
+    /// so such state is not marked in the visited set, but its sleep set is cached.
+    /// The state is explored again only if it is met with the sleep set,
+    /// which is not the superset of the cached ones.
+    pub fn visit(
+        &self,
+        visited: &mut dyn VisitedSet,
+        cache: &mut SleepCache,
+        hash: HashType,
+    ) -> Visit {
+        if visited.contains(hash) {
+            return Visit::Explored;
+        }
+        if self.steps.is_empty() {
+            visited.insert(hash);
+            return match cache.explored.remove(&hash) {
+                Some(_) => Visit::Again,
+                None => Visit::New,
+            };
+        }
+        match cache.explored.entry(hash) {
+            Entry::Vacant(e) => {
+                e.insert(vec![self.clone()]);
+                Visit::New
+            }
+            Entry::Occupied(mut e) => {
+                let sleeps = e.get_mut();
+                if sleeps.iter().any(|s| s.subset_of(self)) {
+                    return Visit::Explored;
+                }
+                sleeps.retain(|s| !self.subset_of(s));
+                sleeps.push(self.clone());
+                Visit::Again
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Result of the visit of the search state.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Visit {
+    /// State is visited for the first time.
+    New,
+
+    /// State was visited before, but the steps not explored from it
+    /// can be explored with the current sleep set.
+    Again,
+
+    /// State was explored before with the subset of the current sleep set.
+    Explored,
+}
+
+/// Sleep sets of the states, which were explored only with the non-empty sleep sets.
+#[derive(Default)]
+pub(crate) struct SleepCache {
+    explored: HashMap<HashType, Vec<SleepSet>>,
+}
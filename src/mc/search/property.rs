@@ -0,0 +1,313 @@
+//! Temporal properties, which are checked along the search paths
+//! (see [`crate::mc::SearchConfigBuilder::property`]).
+//!
+//! Properties are built from the [`Predicate`]s over the search states.
+//! Predicates can use past-time operators, which are evaluated
+//! over the path led to the state.
+
+use std::{hash::Hash, sync::Arc};
+
+use crate::{model::system::HashType, util::hash::Hasher128};
+
+use super::state::StateView;
+
+////////////////////////////////////////////////////////////////////////////////
+
+type StateFn = Arc<dyn Fn(&StateView) -> bool + Send + Sync>;
+
+#[derive(Clone)]
+enum Node {
+    State(StateFn),
+    Not(usize),
+    And(usize, usize),
+    Or(usize, usize),
+    Previous(usize),
+    Once(usize),
+    Historically(usize),
+    Since(usize, usize),
+}
+
+impl Node {
+    fn shift(self, offset: usize) -> Self {
+        match self {
+            Node::State(f) => Node::State(f),
+            Node::Not(a) => Node::Not(a + offset),
+            Node::And(a, b) => Node::And(a + offset, b + offset),
+            Node::Or(a, b) => Node::Or(a + offset, b + offset),
+            Node::Previous(a) => Node::Previous(a + offset),
+            Node::Once(a) => Node::Once(a + offset),
+            Node::Historically(a) => Node::Historically(a + offset),
+            Node::Since(a, b) => Node::Since(a + offset, b + offset),
+        }
+    }
+}
+
+/// Predicate over the search state and the path led to it.
+#[derive(Clone)]
+pub struct Predicate {
+    nodes: Vec<Node>, // subformulas go before formulas, the root is the last
+}
+
+impl Predicate {
+    /// Make predicate, which checks the current state.
+    pub fn new(f: impl Fn(&StateView) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            nodes: vec![Node::State(Arc::new(f))],
+        }
+    }
+
+    fn root(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    fn unary(mut self, make: impl FnOnce(usize) -> Node) -> Self {
+        let node = make(self.root());
+        self.nodes.push(node);
+        self
+    }
+
+    fn binary(mut self, other: Self, make: impl FnOnce(usize, usize) -> Node) -> Self {
+        let a = self.root();
+        let offset = self.nodes.len();
+        self.nodes
+            .extend(other.nodes.into_iter().map(|n| n.shift(offset)));
+        let b = self.root();
+        self.nodes.push(make(a, b));
+        self
+    }
+
+    /// Predicate does not hold.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        self.unary(Node::Not)
+    }
+
+    /// Both predicates hold.
+    pub fn and(self, other: Self) -> Self {
+        self.binary(other, Node::And)
+    }
+
+    /// Any of predicates holds.
+    pub fn or(self, other: Self) -> Self {
+        self.binary(other, Node::Or)
+    }
+
+    /// If predicate holds, then other predicate holds too.
+    pub fn implies(self, other: Self) -> Self {
+        self.not().or(other)
+    }
+
+    /// Predicate held in the previous state of the path.
+    /// Does not hold in the first state.
+    pub fn previous(self) -> Self {
+        self.unary(Node::Previous)
+    }
+
+    /// Predicate held in some state of the path, including the current one.
+    pub fn once(self) -> Self {
+        self.unary(Node::Once)
+    }
+
+    /// Predicate held in all states of the path, including the current one.
+    pub fn historically(self) -> Self {
+        self.unary(Node::Historically)
+    }
+
+    /// Other predicate held in some state of the path,
+    /// and this predicate holds in all states after it.
+    pub fn since(self, other: Self) -> Self {
+        self.binary(other, Node::Since)
+    }
+
+    /// Evaluates values of all subformulas in the state,
+    /// using their values in the previous state of the path.
+    fn eval(&self, view: &StateView, prev: Option<&[bool]>) -> Vec<bool> {
+        let mut now: Vec<bool> = Vec::with_capacity(self.nodes.len());
+        for (i, node) in self.nodes.iter().enumerate() {
+            let value = match node {
+                Node::State(f) => f(view),
+                Node::Not(a) => !now[*a],
+                Node::And(a, b) => now[*a] && now[*b],
+                Node::Or(a, b) => now[*a] || now[*b],
+                Node::Previous(a) => prev.map(|p| p[*a]).unwrap_or(false),
+                Node::Once(a) => now[*a] || prev.map(|p| p[i]).unwrap_or(false),
+                Node::Historically(a) => now[*a] && prev.map(|p| p[i]).unwrap_or(true),
+                Node::Since(a, b) => now[*b] || (now[*a] && prev.map(|p| p[i]).unwrap_or(false)),
+            };
+            now.push(value);
+        }
+        now
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone)]
+enum PropertyKind {
+    Always(Predicate),
+    Eventually(Predicate),
+    LeadsTo(Predicate, Predicate),
+}
+
+/// Temporal property of the system, which is checked along the search paths.
+///
+/// Violations of the future-time properties are found in the terminal states,
+/// which have no available steps, in the states, in which the goal is achieved,
+/// or in the cycles of the explored states graph, in which the obligation
+/// is never satisfied (with the fairness constraints, only in the fair cycles).
+#[derive(Clone)]
+pub struct Property {
+    name: String,
+    kind: PropertyKind,
+}
+
+impl Property {
+    /// Predicate holds in every state.
+    pub fn always(name: impl Into<String>, p: Predicate) -> Self {
+        Self {
+            name: name.into(),
+            kind: PropertyKind::Always(p),
+        }
+    }
+
+    /// Predicate holds in some state of every path.
+    pub fn eventually(name: impl Into<String>, p: Predicate) -> Self {
+        Self {
+            name: name.into(),
+            kind: PropertyKind::Eventually(p),
+        }
+    }
+
+    /// Every state, in which the first predicate holds, is followed
+    /// by the state (maybe, the same), in which the second predicate holds.
+    pub fn leads_to(name: impl Into<String>, p: Predicate, q: Predicate) -> Self {
+        Self {
+            name: name.into(),
+            kind: PropertyKind::LeadsTo(p, q),
+        }
+    }
+
+    /// Returns name of the property.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// State of the property monitor along the path.
+#[derive(Clone, Default, Hash)]
+struct PropertyState {
+    memory: [Option<Vec<bool>>; 2], // values of the predicates subformulas
+    pending_since: Option<usize>,   // depth, since which the obligation is not satisfied
+    satisfied: bool,
+}
+
+impl PropertyState {
+    fn eval(&mut self, i: usize, p: &Predicate, view: &StateView) -> bool {
+        let now = p.eval(view, self.memory[i].as_deref());
+        let value = now[p.root()];
+        self.memory[i] = Some(now);
+        value
+    }
+}
+
+/// Monitors properties along the search path.
+#[derive(Clone, Default)]
+pub(crate) struct PathMonitor {
+    states: Vec<PropertyState>,
+    path: Vec<(HashType, usize)>, // hashes and depths of the states of the path
+}
+
+impl PathMonitor {
+    /// Updates monitor with the next state of the path.
+    /// Returns name of the property violated in the state.
+    pub fn step(&mut self, properties: &[Property], view: &StateView) -> Result<(), String> {
+        if self.states.len() != properties.len() {
+            self.states = vec![Default::default(); properties.len()];
+        }
+        let depth = view.depth();
+        for (property, state) in properties.iter().zip(self.states.iter_mut()) {
+            match &property.kind {
+                PropertyKind::Always(p) => {
+                    if !state.eval(0, p, view) {
+                        return Err(property.name.clone());
+                    }
+                }
+                PropertyKind::Eventually(p) => {
+                    if state.satisfied {
+                        continue;
+                    }
+                    if state.eval(0, p, view) {
+                        state.satisfied = true;
+                        state.pending_since = None;
+                    } else {
+                        state.pending_since.get_or_insert(depth);
+                    }
+                }
+                PropertyKind::LeadsTo(p, q) => {
+                    let triggered = state.eval(0, p, view);
+                    if state.eval(1, q, view) {
+                        state.pending_since = None;
+                    } else if triggered {
+                        state.pending_since.get_or_insert(depth);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Combines hash of the system state with the state of the monitor,
+    /// so states with different obligations are not considered the same.
+    pub fn hash(&self, system_hash: HashType) -> HashType {
+        if self.states.is_empty() {
+            return system_hash;
+        }
+        let mut hasher = Hasher128::new();
+        system_hash.hash(&mut hasher);
+        for state in self.states.iter() {
+            state.memory.hash(&mut hasher);
+            state.pending_since.is_some().hash(&mut hasher);
+            state.satisfied.hash(&mut hasher);
+        }
+        hasher.finish128()
+    }
+
+    /// Returns name of the property, obligation of which is not satisfied
+    /// in the state, where the path ends (the terminal state or the goal).
+    pub fn pending(&self, properties: &[Property]) -> Option<String> {
+        properties
+            .iter()
+            .zip(self.states.iter())
+            .find(|(_, s)| s.pending_since.is_some())
+            .map(|(p, _)| p.name.clone())
+    }
+
+    /// Returns which properties obligations are not satisfied in the state.
+    pub fn obligations(&self) -> Vec<bool> {
+        self.states
+            .iter()
+            .map(|s| s.pending_since.is_some())
+            .collect()
+    }
+
+    /// Checks if the state with provided hash closes the cycle on the path,
+    /// in which some obligation is never satisfied.
+    /// Returns name of the property and the depth, at which the cycle starts.
+    pub fn lasso(&self, properties: &[Property], hash: HashType) -> Option<(String, usize)> {
+        let (_, start) = self.path.iter().find(|(h, _)| *h == hash)?;
+        properties
+            .iter()
+            .zip(self.states.iter())
+            .find(|(_, s)| s.pending_since.is_some_and(|d| d <= *start))
+            .map(|(p, _)| (p.name.clone(), *start))
+    }
+
+    /// Adds state to the path.
+    pub fn push(&mut self, hash: HashType, depth: usize) {
+        if !self.states.is_empty() {
+            self.path.push((hash, depth));
+        }
+    }
+}
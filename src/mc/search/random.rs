@@ -0,0 +1,292 @@
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+use super::{
+    budget::SearchBudget,
+    config::SearchConfig,
+    control::{AbstractionFn, GoalFn, InvariantFn, PruneFn},
+    error::{AllPruned, InvariantViolation, LivenessViolation, SearchError, SearchErrorKind},
+    log::{SearchClock, SearchLog},
+    searcher::{CollectInfo, Searcher},
+    state::{SearchState, StateTrace},
+    transition::Transitions,
+    visited::VisitedSet,
+};
+
+use crate::mc::StateView;
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Represents searcher, which makes random walks through the state space.
+///
+/// Every walk starts from the random start state and makes random steps
+/// until the goal is achieved, the state is pruned, there are no available steps
+/// or the max depth of the config is reached. Invariant is checked in every visited state.
+///
+/// The search is not exhaustive, so the walks reached the max depth
+/// are not considered as errors, unlike the exhausted visited states and time budgets.
+/// Searcher with the same seed makes the same walks.
+///
+/// Temporal properties, fairness constraints and the keep going mode are not supported.
+pub struct RandomWalkSearcher {
+    cfg: SearchConfig,
+    rng: SmallRng,
+    walks: usize,
+}
+
+impl RandomWalkSearcher {
+    /// Make new searcher with provided config, which makes `walks` random walks.
+    /// The config must set the max depth of the walks.
+    pub fn new(cfg: SearchConfig, seed: u64, walks: usize) -> Self {
+        assert!(
+            cfg.max_depth.is_some(),
+            "max depth of the random walks is not set"
+        );
+        assert!(
+            cfg.properties.is_empty(),
+            "temporal properties are not checked by random walks"
+        );
+        assert!(
+            cfg.fairness.is_empty(),
+            "fairness constraints are not checked by random walks"
+        );
+        assert!(
+            cfg.max_violations.is_none(),
+            "keep going mode is not supported by random walks"
+        );
+        Self {
+            cfg,
+            rng: SmallRng::seed_from_u64(seed),
+            walks,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Check,
+    Collect,
+}
+
+/// The way the random walk ends.
+enum WalkEnd {
+    GoalAchieved(StateTrace),
+    Pruned(AllPruned),
+    Stopped,
+}
+
+impl RandomWalkSearcher {
+    #[allow(clippy::too_many_arguments)]
+    fn walk(
+        &mut self,
+        start: &[StateTrace],
+        mode: Mode,
+        visited: &mut dyn VisitedSet,
+        log: &mut SearchLog,
+        clock: &mut SearchClock,
+        budget: &SearchBudget,
+        invariant: &impl InvariantFn,
+        transitions: &Transitions,
+        prune: &impl PruneFn,
+        goal: &impl GoalFn,
+        abstraction: &impl AbstractionFn,
+    ) -> Result<WalkEnd, SearchError> {
+        let mut v = start[self.rng.random_range(0..start.len())].clone();
+        let mut state = SearchState::from_trace(&v).map_err(|k| SearchError::new(k, log))?;
+        log.replay(v.depth());
+        loop {
+            budget.check(log, &v, &state)?;
+            log.visit(&v);
+            clock.tick(log, 1);
+            let system = state.system.handle();
+
+            // make state view
+            let view = StateView::new(&state, v.clone());
+
+            let already_meet = !visited.insert(abstraction(view.clone()));
+            if !already_meet {
+                log.visited_unique += 1;
+            }
+
+            // check invariant and the transition, the walk makes steps in the same state,
+            // so the previous state is replayed from its trace
+            let transition = transitions.check(None, &view);
+            invariant(view.clone()).and(transition).map_err(|report| {
+                let err = InvariantViolation {
+                    trace: v.clone(),
+                    log: system.log(),
+                    report,
+                };
+                let kind = SearchErrorKind::InvariantViolation(err);
+                SearchError::new(kind, log)
+            })?;
+
+            // check goal and prune
+            let goal_check_result = match mode {
+                Mode::Check => {
+                    let result = goal(view.clone());
+                    if result.is_ok() {
+                        return Ok(WalkEnd::GoalAchieved(v));
+                    }
+                    if prune(view) {
+                        log.pruned += 1;
+                        return Ok(WalkEnd::Pruned(AllPruned::new(v, system.log())));
+                    }
+                    result
+                }
+                Mode::Collect => {
+                    if prune(view.clone()) {
+                        log.pruned += 1;
+                        return Ok(WalkEnd::Pruned(AllPruned::new(v, system.log())));
+                    }
+                    let result = goal(view);
+                    if result.is_ok() {
+                        // state is collected only once
+                        if already_meet {
+                            return Ok(WalkEnd::Stopped);
+                        }
+                        return Ok(WalkEnd::GoalAchieved(v));
+                    }
+                    result
+                }
+            };
+
+            // error if no transitions available
+            let steps = state.gen.borrow().steps(system.clone(), &self.cfg);
+            if steps.is_empty() {
+                if let Mode::Collect = mode {
+                    return Ok(WalkEnd::Stopped);
+                }
+                let err = LivenessViolation::new(v, system.log(), goal_check_result.unwrap_err());
+                let err = SearchErrorKind::LivenessViolation(err);
+                return Err(SearchError::new(err, log));
+            }
+
+            if self.cfg.max_depth.is_some_and(|d| v.depth() >= d) {
+                return Ok(WalkEnd::Stopped);
+            }
+
+            // make random step
+            let step = steps[self.rng.random_range(0..steps.len())].clone();
+            log.branch([&step]);
+            v.add_step(step);
+            state = state.resume(&v).map_err(|k| SearchError::new(k, log))?;
+            log.replay(1);
+
+            // make random choices of the processes
+            loop {
+                let mut other = state.branch_choices(&mut v);
+                if other.is_empty() {
+                    break;
+                }
+                log.choose(&other);
+                let i = self.rng.random_range(0..=other.len());
+                if i == other.len() {
+                    break;
+                }
+                v = other.swap_remove(i);
+                state = SearchState::from_trace(&v).map_err(|k| SearchError::new(k, log))?;
+                log.replay(v.depth());
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl Searcher for RandomWalkSearcher {
+    fn check(
+        &mut self,
+        start: Vec<StateTrace>,
+        visited: &mut dyn VisitedSet,
+        invariant: impl InvariantFn,
+        transitions: &Transitions,
+        prune: impl PruneFn,
+        goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
+    ) -> Result<SearchLog, SearchError> {
+        let mut log = SearchLog::new();
+        let mut clock = SearchClock::new(&self.cfg);
+        let budget = SearchBudget::new(&self.cfg);
+        if start.is_empty() {
+            return Ok(log);
+        }
+
+        let mut last_prune = None;
+        let mut all_pruned = true;
+        for _ in 0..self.walks {
+            let end = self.walk(
+                &start,
+                Mode::Check,
+                visited,
+                &mut log,
+                &mut clock,
+                &budget,
+                &invariant,
+                transitions,
+                &prune,
+                &goal,
+                &abstraction,
+            )?;
+            match end {
+                WalkEnd::Pruned(pruned) => last_prune = Some(pruned),
+                _ => all_pruned = false,
+            }
+        }
+
+        match last_prune {
+            Some(last_prune) if all_pruned => {
+                let err = SearchErrorKind::AllPruned(last_prune);
+                Err(SearchError::new(err, &log))
+            }
+            _ => Ok(log),
+        }
+    }
+
+    fn collect(
+        &mut self,
+        start: Vec<StateTrace>,
+        visited: &mut dyn VisitedSet,
+        invariant: impl InvariantFn,
+        transitions: &Transitions,
+        prune: impl PruneFn,
+        goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
+    ) -> Result<CollectInfo, SearchError> {
+        let mut log = SearchLog::new();
+        let mut clock = SearchClock::new(&self.cfg);
+        let budget = SearchBudget::new(&self.cfg);
+        let mut collected = Vec::new();
+        if start.is_empty() {
+            return Ok(CollectInfo {
+                states: collected,
+                log,
+            });
+        }
+
+        for _ in 0..self.walks {
+            let end = self.walk(
+                &start,
+                Mode::Collect,
+                visited,
+                &mut log,
+                &mut clock,
+                &budget,
+                &invariant,
+                transitions,
+                &prune,
+                &goal,
+                &abstraction,
+            )?;
+            if let WalkEnd::GoalAchieved(v) = end {
+                collected.push(v);
+            }
+        }
+
+        Ok(CollectInfo {
+            states: collected,
+            log,
+        })
+    }
+}
@@ -0,0 +1,203 @@
+//! Directed search along the partial schedule,
+//! which allows to reach the known suspicious region of the state space
+//! before the exhaustive search (see [`crate::mc::ModelChecker::follow`]).
+
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{mc::StateView, Address};
+
+use super::{
+    config::SearchConfig,
+    control::InvariantFn,
+    error::{InvariantViolation, ScheduleUnmatched, SearchError, SearchErrorKind},
+    gen::{EventRoute, Generator},
+    log::SearchLog,
+    searcher::CollectInfo,
+    state::{SearchState, StateTrace},
+    step::StateTraceStep,
+    transition::Transitions,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Constraint of the schedule, which matches the steps available in the state
+/// by the kind of step, the source and the target processes and the rpc tag.
+/// Constraint without the filters matches any step.
+///
+/// Kinds are named the same way as in the search statistics
+/// (see [`crate::mc::SearchLog::steps`]), for example `SelectUdp` or `SelectTimer`.
+#[derive(Clone, Default, Debug)]
+pub struct ScheduleConstraint {
+    kind: Option<String>,
+    from: Option<Address>,
+    to: Option<Address>,
+    tag: Option<u64>,
+}
+
+impl ScheduleConstraint {
+    /// Make constraint, which matches any step.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Match steps of the provided kind.
+    pub fn kind(mut self, kind: impl Into<String>) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
+
+    /// Match steps delivering messages sent by the process.
+    pub fn from(mut self, from: impl Into<Address>) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+
+    /// Match steps handled by the process.
+    pub fn to(mut self, to: impl Into<Address>) -> Self {
+        self.to = Some(to.into());
+        self
+    }
+
+    /// Match steps delivering rpc requests with the provided tag.
+    pub fn tag(mut self, tag: u64) -> Self {
+        self.tag = Some(tag);
+        self
+    }
+
+    /// Match delivery of the UDP message sent from one process to another.
+    pub fn udp(from: impl Into<Address>, to: impl Into<Address>) -> Self {
+        Self::new().kind("SelectUdp").from(from).to(to)
+    }
+
+    /// Match firing of the timer of the process.
+    pub fn timer(proc: impl Into<Address>) -> Self {
+        Self::new().kind("SelectTimer").to(proc)
+    }
+
+    fn matches(&self, step: &StateTraceStep, gen: &Generator) -> bool {
+        if self
+            .kind
+            .as_deref()
+            .is_some_and(|k| k != step.inner().kind())
+        {
+            return false;
+        }
+        if self.from.is_none() && self.to.is_none() && self.tag.is_none() {
+            return true;
+        }
+        let route = match step.inner() {
+            StateTraceStep::SendLocal(msg) => Some(EventRoute::to(msg.to())),
+            _ => step.event_id().and_then(|i| gen.event_route(i).cloned()),
+        };
+        let Some(route) = route else {
+            return false;
+        };
+        self.from
+            .as_ref()
+            .is_none_or(|a| route.from.as_ref() == Some(a))
+            && self.to.as_ref().is_none_or(|a| a == &route.to)
+            && self.tag.is_none_or(|t| route.tag == Some(t))
+    }
+}
+
+impl Display for ScheduleConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind.as_deref().unwrap_or("any step"))?;
+        if let Some(from) = self.from.as_ref() {
+            write!(f, " from {}", from)?;
+        }
+        if let Some(to) = self.to.as_ref() {
+            write!(f, " to {}", to)?;
+        }
+        if let Some(tag) = self.tag {
+            write!(f, " with tag {}", tag)?;
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Follow the schedule from the start states.
+/// If several steps match the constraint, all of them are followed,
+/// as well as all choices made by the processes during the steps.
+/// Returns states reached after the last constraint.
+pub(crate) fn follow(
+    start: Vec<StateTrace>,
+    schedule: &[ScheduleConstraint],
+    cfg: &SearchConfig,
+    invariant: impl InvariantFn,
+    transitions: &Transitions,
+) -> Result<CollectInfo, SearchError> {
+    let mut log = SearchLog::new();
+    let mut states = start;
+    for (index, constraint) in schedule.iter().enumerate() {
+        let mut visited = HashSet::new();
+        let mut next = Vec::new();
+        let mut unmatched = None;
+        for v in states {
+            let state = SearchState::from_trace(&v).map_err(|k| SearchError::new(k, &log))?;
+            log.replay(v.depth());
+            let steps: Vec<StateTraceStep> = state
+                .steps(cfg)
+                .into_iter()
+                .filter(|s| constraint.matches(s, &state.gen.borrow()))
+                .collect();
+            if steps.is_empty() {
+                unmatched = Some((v, state.system.handle().log()));
+                continue;
+            }
+            log.branch(steps.iter());
+            let mut children = steps
+                .into_iter()
+                .map(|step| {
+                    let mut u = v.clone();
+                    u.add_step(step);
+                    u
+                })
+                .collect::<Vec<_>>();
+            while let Some(mut u) = children.pop() {
+                let child = SearchState::from_trace(&u).map_err(|k| SearchError::new(k, &log))?;
+                log.replay(u.depth());
+
+                // branch on the other choices made by the last step
+                let choices = child.branch_choices(&mut u);
+                log.choose(&choices);
+                children.extend(choices);
+
+                log.visit(&u);
+                let system = child.system.handle();
+                if !visited.insert(system.hash()) {
+                    continue;
+                }
+                log.visited_unique += 1;
+                let view = StateView::new(&child, u.clone());
+                let transition = transitions.check(Some(&state), &view);
+                invariant(view).and(transition).map_err(|report| {
+                    let err = InvariantViolation {
+                        trace: u.clone(),
+                        log: system.log(),
+                        report,
+                    };
+                    SearchError::new(SearchErrorKind::InvariantViolation(err), &log)
+                })?;
+                next.push(u);
+            }
+        }
+        if next.is_empty() {
+            let (trace, system_log) = unmatched.unwrap_or_default();
+            let err = ScheduleUnmatched {
+                index,
+                constraint: constraint.to_string(),
+                trace,
+                log: system_log,
+            };
+            return Err(SearchError::new(
+                SearchErrorKind::ScheduleUnmatched(err),
+                &log,
+            ));
+        }
+        states = next;
+    }
+    Ok(CollectInfo { states, log })
+}
@@ -1,12 +1,10 @@
-use std::collections::HashSet;
-
-use crate::model::system::HashType;
-
 use super::{
-    control::{GoalFn, InvariantFn, PruneFn},
+    control::{AbstractionFn, GoalFn, InvariantFn, PruneFn},
     error::SearchError,
     log::SearchLog,
     state::StateTrace,
+    transition::Transitions,
+    visited::VisitedSet,
 };
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -19,21 +17,27 @@ pub struct CollectInfo {
 ////////////////////////////////////////////////////////////////////////////////
 
 pub trait Searcher {
+    #[allow(clippy::too_many_arguments)]
     fn check(
         &mut self,
         start: Vec<StateTrace>,
-        visited: &mut HashSet<HashType>,
+        visited: &mut dyn VisitedSet,
         invariant: impl InvariantFn,
+        transitions: &Transitions,
         prune: impl PruneFn,
         goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
     ) -> Result<SearchLog, SearchError>;
 
+    #[allow(clippy::too_many_arguments)]
     fn collect(
         &mut self,
         start: Vec<StateTrace>,
-        visited: &mut HashSet<HashType>,
+        visited: &mut dyn VisitedSet,
         invariant: impl InvariantFn,
+        transitions: &Transitions,
         prune: impl PruneFn,
         goal: impl GoalFn,
+        abstraction: impl AbstractionFn,
     ) -> Result<CollectInfo, SearchError>;
 }
@@ -0,0 +1,188 @@
+//! Minimization of the counterexample traces in the style of delta debugging.
+
+use std::{collections::HashMap, mem::discriminant, ops::Range};
+
+use super::{
+    config::SearchConfig,
+    control::InvariantFn,
+    error::InvariantViolation,
+    state::{SearchState, StateTrace, StateView},
+    step::StateTraceStep,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Shrink trace, which leads to the invariant violation.
+///
+/// Drops `Select*` steps of the trace while the violation is still reproduced.
+/// Then swaps adjacent steps while the violation is still reproduced,
+/// if the swap allows to reach the violation in fewer steps,
+/// for example by moving the fault, which can not be dropped, after the violation.
+/// Events selected by the kept steps are matched with the events registered
+/// during the replay of the reduced trace, so dropping some step can change
+/// ids of the events registered after it.
+///
+/// Steps are matched with the steps available according to the provided config,
+/// which should be the config of the search found the violation.
+///
+/// Returns the violation found on the minimal trace together with its log,
+/// or `None` if the provided trace does not violate the invariant.
+pub fn shrink(
+    trace: &StateTrace,
+    cfg: &SearchConfig,
+    invariant: impl InvariantFn,
+) -> Option<InvariantViolation> {
+    Shrinker::new(trace, cfg, invariant)?.run()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+struct Shrinker<F> {
+    steps: Vec<StateTraceStep>,
+    registered: Vec<Range<usize>>, // ids of events registered by each step
+    invariant: F,
+    cfg: SearchConfig,
+}
+
+impl<F: InvariantFn> Shrinker<F> {
+    fn new(trace: &StateTrace, cfg: &SearchConfig, invariant: F) -> Option<Self> {
+        let steps = trace.steps().to_vec();
+        let mut registered = Vec::with_capacity(steps.len());
+        let mut state = SearchState::from_trace(&StateTrace::new()).ok()?;
+        for step in steps.iter() {
+            let before = state.system.handle().events_count();
+            step.apply(&mut state).ok()?;
+            let after = state.system.handle().events_count();
+            registered.push(before..after);
+        }
+        Some(Self {
+            steps,
+            registered,
+            invariant,
+            cfg: cfg.clone(),
+        })
+    }
+
+    fn run(self) -> Option<InvariantViolation> {
+        let mut kept: Vec<usize> = (0..self.steps.len()).collect();
+        let best = self.check(&kept)?;
+        kept.truncate(best.trace.depth());
+
+        let (mut kept, mut best) = self.drop_steps(kept, best);
+        while let Some((swapped, violation)) = self.swap_steps(&kept) {
+            kept = swapped;
+            best = violation;
+        }
+        Some(best)
+    }
+
+    /// Drop `Select*` steps while the violation is still reproduced.
+    fn drop_steps(
+        &self,
+        mut kept: Vec<usize>,
+        mut best: InvariantViolation,
+    ) -> (Vec<usize>, InvariantViolation) {
+        let mut parts = 2;
+        loop {
+            let selects: Vec<usize> = kept
+                .iter()
+                .copied()
+                .filter(|i| self.steps[*i].event_id().is_some())
+                .collect();
+            if selects.is_empty() {
+                break;
+            }
+            parts = parts.min(selects.len());
+
+            let mut reduced = false;
+            for part in selects.chunks(selects.len().div_ceil(parts)) {
+                let candidate: Vec<usize> =
+                    kept.iter().copied().filter(|i| !part.contains(i)).collect();
+                if let Some(violation) = self.check(&candidate) {
+                    kept = candidate;
+                    kept.truncate(violation.trace.depth());
+                    best = violation;
+                    parts = (parts - 1).max(2);
+                    reduced = true;
+                    break;
+                }
+            }
+
+            if !reduced {
+                if parts == selects.len() {
+                    break;
+                }
+                parts = (parts * 2).min(selects.len());
+            }
+        }
+        (kept, best)
+    }
+
+    /// Find the swap of adjacent steps, after which the violation is reproduced
+    /// in fewer steps, and drop the steps of the swapped trace.
+    fn swap_steps(&self, kept: &[usize]) -> Option<(Vec<usize>, InvariantViolation)> {
+        (1..kept.len()).find_map(|i| {
+            let mut candidate = kept.to_vec();
+            candidate.swap(i - 1, i);
+            let violation = self.check(&candidate)?;
+            candidate.truncate(violation.trace.depth());
+            let (candidate, violation) = self.drop_steps(candidate, violation);
+            (candidate.len() < kept.len()).then_some((candidate, violation))
+        })
+    }
+
+    /// Replay the steps with provided indices
+    /// and return the first found invariant violation.
+    fn check(&self, kept: &[usize]) -> Option<InvariantViolation> {
+        let mut state = SearchState::from_trace(&StateTrace::new()).ok()?;
+        let mut trace = StateTrace::new();
+        let mut ids = HashMap::new();
+        for i in kept.iter().copied() {
+            let step = self.align(&state, &self.steps[i], &ids)?;
+            let before = state.system.handle().events_count();
+            step.apply(&mut state).ok()?;
+            let after = state.system.handle().events_count();
+            ids.extend(self.registered[i].clone().zip(before..after));
+            trace.add_step(step);
+            state.depth = trace.depth();
+
+            if let Err(report) = (self.invariant)(StateView::new(&state, trace.clone())) {
+                let log = state.system.handle().log();
+                return Some(InvariantViolation { trace, log, report });
+            }
+        }
+        None
+    }
+
+    /// Find the step available in the state, which corresponds to the provided step
+    /// of the original trace.
+    fn align(
+        &self,
+        state: &SearchState,
+        step: &StateTraceStep,
+        ids: &HashMap<usize, usize>,
+    ) -> Option<StateTraceStep> {
+        if let StateTraceStep::Choose(step, choices) = step {
+            let step = self.align(state, step, ids)?;
+            return Some(StateTraceStep::Choose(Box::new(step), choices.clone()));
+        }
+        if let StateTraceStep::Apply(_) = step {
+            return Some(step.clone());
+        }
+        let event_id = match step.event_id() {
+            Some(id) => Some(*ids.get(&id)?),
+            None => None,
+        };
+        state.steps(&self.cfg).into_iter().find(|s| {
+            discriminant(s) == discriminant(step)
+                && match event_id {
+                    Some(id) => s.event_id() == Some(id) && udp_dropped(s) == udp_dropped(step),
+                    None => s.to_string() == step.to_string(),
+                }
+        })
+    }
+}
+
+fn udp_dropped(step: &StateTraceStep) -> bool {
+    matches!(step, StateTraceStep::SelectUdp(_, msg) if msg.drop)
+}
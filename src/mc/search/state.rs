@@ -1,6 +1,7 @@
 use std::{
     cell::RefCell,
     fmt::{Debug, Display},
+    path::Path,
     rc::Rc,
 };
 
@@ -9,13 +10,14 @@ use crate::{
     model::system::System, model::SystemHandle,
 };
 
-use super::{gen::Generator, step::StateTraceStep};
+use super::{gen::Generator, log::SearchLog, step::StateTraceStep, trace::trace_to_json};
 
 ////////////////////////////////////////////////////////////////////////////////
 
 pub struct SearchState {
     pub(crate) system: System,
     pub(crate) gen: Rc<RefCell<Generator>>,
+    pub(crate) depth: usize,
 }
 
 impl SearchState {
@@ -23,9 +25,21 @@ impl SearchState {
         let gen = Rc::new(RefCell::new(Generator::new()));
         let driver = gen.clone() as Rc<RefCell<dyn EventDriver>>;
         let system = System::new_default_net(&driver);
-        let mut state = Self { system, gen };
-        trace.apply_steps(&mut state)?;
-        Ok(state)
+        let state = Self {
+            system,
+            gen,
+            depth: 0,
+        };
+        state.resume(trace)
+    }
+
+    /// Resume state, which was made from the prefix of the provided trace,
+    /// by applying the rest of the trace steps.
+    pub fn resume(mut self, trace: &StateTrace) -> Result<Self, SearchErrorKind> {
+        debug_assert!(self.depth <= trace.depth());
+        trace.apply_steps(self.depth, &mut self)?;
+        self.depth = trace.depth();
+        Ok(self)
     }
 
     pub fn steps(&self, cfg: &SearchConfig) -> Vec<StateTraceStep> {
@@ -33,6 +47,31 @@ impl SearchState {
         self.gen.borrow().steps(system, cfg)
     }
 
+    /// Returns traces, which differ from the provided one by the choices
+    /// made by the processes during the last step (see [`crate::choose`]).
+    /// Choices made in the state are recorded into the last step of the provided trace.
+    pub fn branch_choices(&self, trace: &mut StateTrace) -> Vec<StateTrace> {
+        let gen = self.gen.borrow();
+        let (made, forced) = gen.choices();
+        if made.len() <= forced || trace.steps.is_empty() {
+            return Vec::new();
+        }
+        let step = trace.steps.pop().unwrap().inner().clone();
+        let values: Vec<usize> = made.iter().map(|(value, _)| *value).collect();
+        let mut other = Vec::new();
+        for (i, (_, options)) in made.iter().enumerate().skip(forced) {
+            for value in 1..*options {
+                let mut choices = values[..i].to_vec();
+                choices.push(value);
+                let mut u = trace.clone();
+                u.add_step(StateTraceStep::Choose(Box::new(step.clone()), choices));
+                other.push(u);
+            }
+        }
+        trace.add_step(StateTraceStep::Choose(Box::new(step), values));
+        other
+    }
+
     pub fn select_ready_event(&self, i: usize) {
         self.gen.borrow_mut().select_ready_event(i);
         self.system.handle().run_async_tasks();
@@ -41,6 +80,70 @@ impl SearchState {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Keeps states of the search, from which the children states can be resumed
+/// instead of replaying their traces from the beginning (see [`SearchState::resume`]).
+///
+/// The system model can not be cloned, so every snapshot
+/// can be resumed only once. Snapshots shared by the children
+/// are only read (see [`crate::mc::ModelChecker::transition_invariant`]).
+pub(crate) struct Snapshots {
+    limit: usize,
+    stored: usize,
+}
+
+impl Snapshots {
+    pub fn new(cfg: &SearchConfig) -> Self {
+        Self {
+            limit: cfg.max_snapshots.unwrap_or(0),
+            stored: 0,
+        }
+    }
+
+    /// Store state as snapshot if the limit of stored snapshots is not reached.
+    pub fn store(&mut self, state: SearchState) -> Option<SearchState> {
+        if self.stored < self.limit {
+            self.stored += 1;
+            Some(state)
+        } else {
+            None
+        }
+    }
+
+    /// Store state as the previous state of the transitions to its children,
+    /// which is shared by them, if the limit of stored snapshots is not reached.
+    pub fn share(&mut self, state: SearchState) -> Option<Rc<SearchState>> {
+        self.store(state).map(Rc::new)
+    }
+
+    /// Release the shared state when the transition to its child is checked.
+    /// The snapshot is freed when the last child is checked.
+    pub fn release(&mut self, prev: Option<Rc<SearchState>>) {
+        if prev.is_some_and(|state| Rc::strong_count(&state) == 1) {
+            self.stored -= 1;
+        }
+    }
+
+    /// Restore state of the trace from the snapshot if it is present,
+    /// or replay the trace from the beginning.
+    pub fn restore(
+        &mut self,
+        snapshot: Option<SearchState>,
+        trace: &StateTrace,
+        log: &mut SearchLog,
+    ) -> Result<SearchState, SearchErrorKind> {
+        log.replay(trace.depth() - snapshot.as_ref().map_or(0, |s| s.depth));
+        match snapshot {
+            Some(state) => {
+                self.stored -= 1;
+                state.resume(trace)
+            }
+            None => SearchState::from_trace(trace),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Clone, Default)]
 pub struct StateTrace {
     steps: Vec<StateTraceStep>,
@@ -63,8 +166,25 @@ impl StateTrace {
         self.steps.get(i).unwrap()
     }
 
-    fn apply_steps(&self, state: &mut SearchState) -> Result<(), SearchErrorKind> {
-        for i in 0..self.steps.len() {
+    pub(crate) fn steps(&self) -> &[StateTraceStep] {
+        &self.steps
+    }
+
+    /// Serialize trace into the stable JSON format.
+    /// Apply steps are referenced by their names
+    /// (see [`crate::mc::ModelChecker::apply_named`]).
+    pub fn to_json(&self) -> String {
+        trace_to_json(self)
+    }
+
+    /// Save trace in the JSON format into the file,
+    /// which can be replayed later with [`crate::mc::ModelChecker::replay`].
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    fn apply_steps(&self, from: usize, state: &mut SearchState) -> Result<(), SearchErrorKind> {
+        for i in from..self.steps.len() {
             let step = &self.steps[i];
             let mut apply_result = step.apply(state);
             if let Err(SearchErrorKind::ProcessPanic(p)) = apply_result.as_mut() {
@@ -121,4 +241,8 @@ impl StateView {
     pub fn depth(&self) -> usize {
         self.trace.steps.len()
     }
+
+    pub(crate) fn trace(&self) -> &StateTrace {
+        &self.trace
+    }
 }
@@ -4,6 +4,8 @@ use std::{
     time::Duration,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
     mc::error::SearchErrorKind,
     model::event::{
@@ -11,13 +13,14 @@ use crate::{
         outcome::{EventOutcome, EventOutcomeKind},
     },
     model::fs::event::FsEventOutcome,
+    Address,
 };
 
 use super::{control::ApplyFunctor, error::ProcessPanic, state::SearchState};
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct UdpMessage {
     pub event_id: usize,
     pub udp_msg_id: usize,
@@ -27,7 +30,7 @@ pub struct UdpMessage {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Timer {
     pub event_id: usize,
     pub timer_id: usize,
@@ -36,7 +39,7 @@ pub struct Timer {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TcpPacket {
     pub event_id: usize,
     pub tcp_msg_id: usize,
@@ -45,7 +48,7 @@ pub struct TcpPacket {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TcpEvent {
     pub event_id: usize,
     pub kind: TcpEventKind,
@@ -54,7 +57,7 @@ pub struct TcpEvent {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FsEvent {
     pub event_id: usize,
     pub outcome: FsEventOutcome,
@@ -63,7 +66,7 @@ pub struct FsEvent {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RpcMessage {
     pub event_id: usize,
     pub rpc_request_id: u64,
@@ -72,7 +75,7 @@ pub struct RpcMessage {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RpcEvent {
     pub event_id: usize,
     pub kind: RpcEventKind,
@@ -81,6 +84,23 @@ pub struct RpcEvent {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Local message of the workload (see [`crate::mc::Workload`]).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LocalMessage {
+    pub id: usize,
+    pub node: String,
+    pub process: String,
+    pub content: String,
+}
+
+impl LocalMessage {
+    pub fn to(&self) -> Address {
+        Address::new(self.node.clone(), self.process.clone())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Clone)]
 pub enum StateTraceStep {
     SelectUdp(usize, UdpMessage),
@@ -90,14 +110,81 @@ pub enum StateTraceStep {
     SelectRpcMessage(usize, RpcMessage),
     SelectRpcEvent(usize, RpcEvent),
     SelectFsEvent(usize, FsEvent),
-    CrashNode(usize),    // id of node
-    ShutdownNode(usize), // id of node
+    FailFsEvent(usize, FsEvent),
+    CrashNode(usize),      // id of node
+    ShutdownNode(usize),   // id of node
+    RestartNode(usize),    // id of node
+    CrashFs(usize),        // id of node
+    Partition(Vec<usize>), // ids of nodes separated from the others
+    Heal,
     Apply(Box<dyn ApplyFunctor>),
+    SendLocal(LocalMessage),
+    Choose(Box<StateTraceStep>, Vec<usize>), // step with the forced choices of processes
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 
 impl StateTraceStep {
+    /// Returns the step without the forced choices.
+    pub(crate) fn inner(&self) -> &StateTraceStep {
+        match self {
+            Self::Choose(step, _) => step.inner(),
+            _ => self,
+        }
+    }
+
+    /// Returns id of the event, which is selected by the step.
+    pub(crate) fn event_id(&self) -> Option<usize> {
+        match self.inner() {
+            Self::SelectUdp(i, _)
+            | Self::SelectTimer(i, _)
+            | Self::SelectTcpPacket(i, _)
+            | Self::SelectTcpEvent(i, _)
+            | Self::SelectRpcMessage(i, _)
+            | Self::SelectRpcEvent(i, _)
+            | Self::SelectFsEvent(i, _)
+            | Self::FailFsEvent(i, _) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns name of the step kind used in the search statistics.
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Self::SelectUdp(..) => "SelectUdp",
+            Self::SelectTimer(..) => "SelectTimer",
+            Self::SelectTcpPacket(..) => "SelectTcpPacket",
+            Self::SelectTcpEvent(..) => "SelectTcpEvent",
+            Self::SelectRpcMessage(..) => "SelectRpcMessage",
+            Self::SelectRpcEvent(..) => "SelectRpcEvent",
+            Self::SelectFsEvent(..) => "SelectFsEvent",
+            Self::FailFsEvent(..) => "FailFsEvent",
+            Self::CrashNode(_) => "CrashNode",
+            Self::ShutdownNode(_) => "ShutdownNode",
+            Self::RestartNode(_) => "RestartNode",
+            Self::CrashFs(_) => "CrashFs",
+            Self::Partition(_) => "Partition",
+            Self::Heal => "Heal",
+            Self::Apply(_) => "Apply",
+            Self::SendLocal(_) => "SendLocal",
+            Self::Choose(..) => "Choose",
+        }
+    }
+
+    /// Returns name of the fault injected by the step.
+    pub(crate) fn fault(&self) -> Option<&'static str> {
+        let step = self.inner();
+        match step {
+            Self::SelectUdp(_, msg) if msg.drop => Some("DropUdp"),
+            Self::FailFsEvent(..)
+            | Self::CrashNode(_)
+            | Self::ShutdownNode(_)
+            | Self::CrashFs(_)
+            | Self::Partition(_) => Some(step.kind()),
+            _ => None,
+        }
+    }
+
     fn apply_event_outcome(
         &self,
         state: &mut SearchState,
@@ -106,10 +193,14 @@ impl StateTraceStep {
     ) -> Result<(), SearchErrorKind> {
         state.gen.borrow_mut().select_ready_event(i);
         let handle = state.system.handle();
-        std::panic::catch_unwind(AssertUnwindSafe(move || {
-            handle.handle_event_outcome(outcome);
-        }))
-        .map_err(|_| {
+        Self::catch_process_panic(state, move || handle.handle_event_outcome(outcome))
+    }
+
+    fn catch_process_panic(
+        state: &mut SearchState,
+        f: impl FnOnce(),
+    ) -> Result<(), SearchErrorKind> {
+        std::panic::catch_unwind(AssertUnwindSafe(f)).map_err(|_| {
             let p = ProcessPanic {
                 trace: None,
                 log: state.system.handle().log(),
@@ -119,6 +210,15 @@ impl StateTraceStep {
     }
 
     pub fn apply(&self, state: &mut SearchState) -> Result<(), SearchErrorKind> {
+        let forced = match self {
+            StateTraceStep::Choose(_, choices) => choices.as_slice(),
+            _ => &[],
+        };
+        state.gen.borrow_mut().force_choices(forced);
+        self.inner().apply_step(state)
+    }
+
+    fn apply_step(&self, state: &mut SearchState) -> Result<(), SearchErrorKind> {
         match self {
             StateTraceStep::SelectUdp(i, msg) => {
                 let kind = if msg.drop {
@@ -187,6 +287,22 @@ impl StateTraceStep {
                 state.system.handle().shutdown_node_index(*node);
                 Ok(())
             }
+            StateTraceStep::RestartNode(node) => {
+                let handle = state.system.handle();
+                Self::catch_process_panic(state, move || handle.restart_node_index(*node))
+            }
+            StateTraceStep::CrashFs(node) => {
+                let handle = state.system.handle();
+                Self::catch_process_panic(state, move || handle.crash_fs_index(*node))
+            }
+            StateTraceStep::Partition(nodes) => {
+                state.system.handle().partition_index(nodes);
+                Ok(())
+            }
+            StateTraceStep::Heal => {
+                state.system.handle().heal();
+                Ok(())
+            }
             StateTraceStep::SelectFsEvent(i, e) => {
                 let outcome = EventOutcome {
                     event_id: e.event_id,
@@ -195,6 +311,25 @@ impl StateTraceStep {
                 };
                 self.apply_event_outcome(state, *i, outcome)
             }
+            StateTraceStep::FailFsEvent(i, e) => {
+                let err = e.outcome.clone().unwrap_err();
+                let outcome = EventOutcome {
+                    event_id: e.event_id,
+                    kind: EventOutcomeKind::FsEventFailed(err),
+                    time: e.time,
+                };
+                self.apply_event_outcome(state, *i, outcome)
+            }
+            StateTraceStep::SendLocal(msg) => {
+                state.gen.borrow_mut().mark_sent(msg.id);
+                let handle = state.system.handle();
+                let to = msg.to();
+                let content = msg.content.clone();
+                Self::catch_process_panic(state, move || {
+                    handle.send_local(&to, content).unwrap();
+                })
+            }
+            StateTraceStep::Choose(step, _) => step.apply_step(state),
         }
     }
 }
@@ -240,6 +375,17 @@ impl Debug for StateTraceStep {
                 .field(arg1)
                 .finish(),
             Self::ShutdownNode(arg0) => f.debug_tuple("SelectShutdownNode").field(arg0).finish(),
+            Self::FailFsEvent(arg0, arg1) => f
+                .debug_tuple("FailFsEvent")
+                .field(arg0)
+                .field(arg1)
+                .finish(),
+            Self::RestartNode(arg0) => f.debug_tuple("RestartNode").field(arg0).finish(),
+            Self::CrashFs(arg0) => f.debug_tuple("CrashFs").field(arg0).finish(),
+            Self::Partition(arg0) => f.debug_tuple("Partition").field(arg0).finish(),
+            Self::Heal => write!(f, "Heal"),
+            Self::SendLocal(arg0) => f.debug_tuple("SendLocal").field(arg0).finish(),
+            Self::Choose(arg0, arg1) => f.debug_tuple("Choose").field(arg0).field(arg1).finish(),
         }
     }
 }
@@ -291,6 +437,27 @@ impl Display for StateTraceStep {
             StateTraceStep::SelectFsEvent(i, _) => {
                 write!(f, "Select {}: Fs event", *i)
             }
+            StateTraceStep::FailFsEvent(i, _) => {
+                write!(f, "Select {}: Fs event failed", *i)
+            }
+            StateTraceStep::RestartNode(node) => {
+                write!(f, "Restart node {}", node)
+            }
+            StateTraceStep::CrashFs(node) => {
+                write!(f, "Crash fs of node {}", node)
+            }
+            StateTraceStep::Partition(nodes) => {
+                write!(f, "Separate nodes {:?}", nodes)
+            }
+            StateTraceStep::Heal => {
+                write!(f, "Heal network")
+            }
+            StateTraceStep::SendLocal(msg) => {
+                write!(f, "Send local message {} to {}", msg.id, msg.to())
+            }
+            StateTraceStep::Choose(step, choices) => {
+                write!(f, "{}, choices {:?}", step, choices)
+            }
         }
     }
 }
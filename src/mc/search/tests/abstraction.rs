@@ -0,0 +1,88 @@
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    mc::{
+        self,
+        search::tests::common::{add_coin, flips},
+        SearchConfigBuilder, StateView,
+    },
+    model::SystemHandle,
+    HashType,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+fn build(system: SystemHandle) {
+    add_coin(&system, 3);
+}
+
+fn finished(v: StateView) -> Result<(), String> {
+    match flips(&v.system()).len() {
+        3 => Ok(()),
+        _ => Err("not finished".into()),
+    }
+}
+
+/// Ignores the order of flips.
+fn heads(v: StateView) -> HashType {
+    let flips = flips(&v.system());
+    let heads = flips.iter().sum::<usize>();
+    (flips.len() * 10 + heads) as HashType
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn coarser_dedup_key() {
+    let cfg = SearchConfigBuilder::no_faults().build();
+    let log = mc::ModelChecker::new_with_build(build)
+        .check(
+            |_| Ok(()),
+            |_| false,
+            finished,
+            mc::DfsSearcher::new(cfg.clone()),
+        )
+        .unwrap();
+    assert_eq!(log.visited_unique, 15);
+    assert!(log.collision_probability() > 0.);
+    assert!(log.collision_probability() < 1e-30);
+
+    let log = mc::ModelChecker::new_with_build(build)
+        .check_with_abstraction(
+            |_| Ok(()),
+            |_| false,
+            finished,
+            heads,
+            mc::DfsSearcher::new(cfg),
+        )
+        .unwrap();
+    assert_eq!(log.visited_unique, 10);
+}
+
+#[test]
+fn collect_one_per_key() {
+    let cfg = SearchConfigBuilder::no_faults().build();
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    checker
+        .collect_with_abstraction(
+            |_| Ok(()),
+            |_| false,
+            finished,
+            heads,
+            mc::BfsSearcher::new(cfg),
+        )
+        .unwrap();
+    assert_eq!(checker.states_count(), 4);
+
+    let result = Arc::new(Mutex::new(BTreeSet::new()));
+    let r = result.clone();
+    checker.for_each(move |system| {
+        r.lock()
+            .unwrap()
+            .insert(flips(&system).iter().sum::<usize>());
+    });
+    assert_eq!(*result.lock().unwrap(), BTreeSet::from([0, 1, 2, 3]));
+}
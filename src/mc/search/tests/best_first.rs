@@ -0,0 +1,167 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use crate::{
+    mc::{
+        self,
+        error::SearchErrorKind,
+        search::tests::common::{self, add_coin},
+        Fairness, Predicate, Property, SearchConfig, SearchConfigBuilder, StateView,
+    },
+    model::SystemHandle,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+const FLIPS: usize = 10;
+
+fn build(system: SystemHandle) {
+    add_coin(&system, FLIPS);
+}
+
+fn flips(v: &StateView) -> Vec<usize> {
+    common::flips(&v.system())
+}
+
+fn heads(v: StateView) -> i64 {
+    flips(&v).iter().sum::<usize>() as i64
+}
+
+fn not_all_heads(v: StateView) -> Result<(), String> {
+    match heads(v) as usize {
+        FLIPS => Err("all heads".into()),
+        _ => Ok(()),
+    }
+}
+
+fn finished(v: StateView) -> Result<(), String> {
+    match flips(&v).len() {
+        FLIPS => Ok(()),
+        _ => Err("not finished".into()),
+    }
+}
+
+fn cfg() -> SearchConfig {
+    SearchConfigBuilder::no_faults()
+        .max_visited_states(100)
+        .build()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn deep_violation_found() {
+    let err = mc::ModelChecker::new_with_build(build)
+        .check(
+            not_all_heads,
+            |_| false,
+            finished,
+            mc::BestFirstSearcher::new(cfg(), heads),
+        )
+        .unwrap_err();
+    let SearchErrorKind::InvariantViolation(violation) = &err.kind else {
+        panic!("unexpected error: {}", err);
+    };
+    assert_eq!(violation.report, "all heads");
+    assert!(err.log.visited_total < 50);
+
+    // the same budget is not enough for the bfs
+    let err = mc::ModelChecker::new_with_build(build)
+        .check(
+            not_all_heads,
+            |_| false,
+            finished,
+            mc::BfsSearcher::new(cfg()),
+        )
+        .unwrap_err();
+    assert!(matches!(err.kind, SearchErrorKind::BudgetExceeded(_)));
+}
+
+#[test]
+fn collect_same_as_bfs() {
+    let cfg = SearchConfigBuilder::no_faults().max_snapshots(4).build();
+    let goal = |v: StateView| match flips(&v).len() {
+        3 => Ok(()),
+        _ => Err("not flipped".into()),
+    };
+
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    let log = checker
+        .collect(
+            |_| Ok(()),
+            |_| false,
+            goal,
+            mc::BestFirstSearcher::new(cfg.clone(), heads),
+        )
+        .unwrap();
+    assert_eq!(checker.states_count(), 8);
+
+    let mut bfs_checker = mc::ModelChecker::new_with_build(build);
+    let bfs_log = bfs_checker
+        .collect(|_| Ok(()), |_| false, goal, mc::BfsSearcher::new(cfg))
+        .unwrap();
+    assert_eq!(bfs_checker.states_count(), 8);
+    assert_eq!(log.visited_unique, bfs_log.visited_unique);
+}
+
+#[test]
+fn scored_once_per_expanded_state() {
+    let scored = Arc::new(AtomicUsize::new(0));
+    let score = {
+        let scored = scored.clone();
+        move |v: StateView| {
+            scored.fetch_add(1, Ordering::SeqCst);
+            heads(v)
+        }
+    };
+    let err = mc::ModelChecker::new_with_build(build)
+        .check(
+            not_all_heads,
+            |_| false,
+            finished,
+            mc::BestFirstSearcher::new(cfg(), score),
+        )
+        .unwrap_err();
+    assert!(matches!(err.kind, SearchErrorKind::InvariantViolation(_)));
+    assert!(scored.load(Ordering::SeqCst) < err.log.visited_total);
+}
+
+#[test]
+fn violations_kept_on_budget_exceeded() {
+    let cfg = SearchConfigBuilder::no_faults()
+        .max_visited_states(100)
+        .keep_going(10)
+        .build();
+    let err = mc::ModelChecker::new_with_build(build)
+        .check(
+            not_all_heads,
+            |_| false,
+            finished,
+            mc::BestFirstSearcher::new(cfg, heads),
+        )
+        .unwrap_err();
+    let SearchErrorKind::InvariantViolations(violations) = &err.kind else {
+        panic!("unexpected error: {}", err);
+    };
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].report, "all heads");
+}
+
+#[should_panic(expected = "temporal properties are not checked by best-first search")]
+#[test]
+fn properties_rejected() {
+    let property = Property::always("any", Predicate::new(|_| true));
+    let cfg = SearchConfigBuilder::no_faults().property(property).build();
+    mc::BestFirstSearcher::new(cfg, heads);
+}
+
+#[should_panic(expected = "fairness constraints are not checked by best-first search")]
+#[test]
+fn fairness_rejected() {
+    let cfg = SearchConfigBuilder::no_faults()
+        .fairness(Fairness::weak())
+        .build();
+    mc::BestFirstSearcher::new(cfg, heads);
+}
@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use crate::{
+    mc::{
+        self,
+        error::{Budget, SearchErrorKind},
+        search::tests::common::add_tickers,
+        SearchConfigBuilder,
+    },
+    model::SystemHandle,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Ticks forever, so the state space is infinite.
+fn build(system: SystemHandle) {
+    add_tickers(&system, &["node"], None);
+}
+
+fn check(cfg: SearchConfigBuilder, dfs: bool) -> mc::error::SearchError {
+    let cfg = cfg.build();
+    let checker = mc::ModelChecker::new_with_build(build);
+    let goal = |_| Err("never".to_string());
+    if dfs {
+        let searcher = mc::DfsSearcher::new(cfg);
+        checker.check(|_| Ok(()), |_| false, goal, searcher)
+    } else {
+        let searcher = mc::BfsSearcher::new(cfg);
+        checker.check(|_| Ok(()), |_| false, goal, searcher)
+    }
+    .unwrap_err()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn max_depth() {
+    for dfs in [false, true] {
+        let err = check(SearchConfigBuilder::no_faults().max_depth(10), dfs);
+        let SearchErrorKind::BudgetExceeded(exceeded) = &err.kind else {
+            panic!("unexpected error: {}", err);
+        };
+        assert_eq!(exceeded.budget, Budget::Depth(10));
+        assert_eq!(exceeded.trace.depth(), 10);
+        assert_eq!(err.log.visited_total, 10);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn max_visited_states() {
+    for dfs in [false, true] {
+        let err = check(SearchConfigBuilder::no_faults().max_visited_states(50), dfs);
+        let SearchErrorKind::BudgetExceeded(exceeded) = &err.kind else {
+            panic!("unexpected error: {}", err);
+        };
+        assert_eq!(exceeded.budget, Budget::VisitedStates(50));
+        assert_eq!(err.log.visited_total, 50);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn time_limit() {
+    let limit = Duration::from_millis(50);
+    let err = check(SearchConfigBuilder::no_faults().time_limit(limit), true);
+    let SearchErrorKind::BudgetExceeded(exceeded) = &err.kind else {
+        panic!("unexpected error: {}", err);
+    };
+    assert_eq!(exceeded.budget, Budget::Time(limit));
+    assert!(err.log.visited_total > 0);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn collect_within_depth() {
+    let cfg = SearchConfigBuilder::no_faults().max_depth(10).build();
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    let goal = |v: mc::StateView| {
+        if v.system().hash() != 0 && v.depth() == 5 {
+            Ok(())
+        } else {
+            Err("not yet".to_string())
+        }
+    };
+    let searcher = mc::BfsSearcher::new(cfg.clone());
+    checker
+        .collect(|_| Ok(()), |_| false, goal, searcher)
+        .unwrap();
+    assert_eq!(checker.states_count(), 1);
+
+    let searcher = mc::BfsSearcher::new(cfg);
+    let err = checker
+        .collect(|_| Ok(()), |_| false, |_| Err("never".into()), searcher)
+        .unwrap_err();
+    assert!(matches!(err.kind, SearchErrorKind::BudgetExceeded(..)));
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[should_panic(expected = "search budgets are not supported by parallel search")]
+#[test]
+fn parallel_rejects_budget() {
+    let cfg = SearchConfigBuilder::no_faults().max_depth(10).build();
+    mc::ParallelSearcher::new(cfg, 2);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn random_walk_budgets() {
+    let walk = |cfg: SearchConfigBuilder| {
+        let searcher = mc::RandomWalkSearcher::new(cfg.build(), 123, 10);
+        let checker = mc::ModelChecker::new_with_build(build);
+        checker.check(|_| Ok(()), |_| false, |_| Err("never".into()), searcher)
+    };
+
+    // walks reached the max depth are not errors
+    let log = walk(SearchConfigBuilder::no_faults().max_depth(10)).unwrap();
+    assert_eq!(log.visited_total, 100);
+
+    let cfg = SearchConfigBuilder::no_faults()
+        .max_depth(10)
+        .max_visited_states(50);
+    let err = walk(cfg).unwrap_err();
+    let SearchErrorKind::BudgetExceeded(exceeded) = &err.kind else {
+        panic!("unexpected error: {}", err);
+    };
+    assert_eq!(exceeded.budget, Budget::VisitedStates(50));
+    assert_eq!(err.log.visited_total, 50);
+}
+
+#[should_panic(expected = "max depth of the random walks is not set")]
+#[test]
+fn random_walk_requires_depth() {
+    let cfg = SearchConfigBuilder::no_faults().build();
+    mc::RandomWalkSearcher::new(cfg, 123, 10);
+}
@@ -0,0 +1,164 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeSet,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    choose,
+    mc::{self, error::SearchErrorKind, ScheduleConstraint, SearchConfigBuilder, StateView},
+    model::{node::Node, SystemHandle},
+    sleep, spawn, Address, HashType, Process,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Makes two choices after the timer is fired.
+struct Chooser {
+    chosen: Rc<RefCell<Vec<usize>>>,
+}
+
+impl Process for Chooser {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, _content: String) {
+        let chosen = self.chosen.clone();
+        spawn(async move {
+            sleep(Duration::from_millis(100)).await;
+            let first = choose(2);
+            let second = choose(3);
+            chosen.borrow_mut().extend([first, second]);
+        });
+    }
+
+    fn hash(&self) -> HashType {
+        self.chosen
+            .borrow()
+            .iter()
+            .fold(1, |h, c| h * 10 + *c as HashType)
+    }
+}
+
+fn build(system: SystemHandle) {
+    let mut node = Node::new("n");
+    let chooser = Chooser {
+        chosen: Default::default(),
+    };
+    node.add_proc("chooser", chooser).unwrap();
+    system.add_node(node).unwrap();
+    system
+        .send_local(&Address::new("n", "chooser"), "choose")
+        .unwrap();
+}
+
+fn chosen(system: &SystemHandle) -> Vec<usize> {
+    let chooser = system
+        .proc_state::<Chooser>(Address::new("n", "chooser"))
+        .unwrap();
+    let chosen = chooser.borrow().chosen.borrow().clone();
+    chosen
+}
+
+fn collected(checker: &mc::ModelChecker) -> BTreeSet<Vec<usize>> {
+    let result = Arc::new(Mutex::new(BTreeSet::new()));
+    let r = result.clone();
+    checker.for_each(move |system| {
+        r.lock().unwrap().insert(chosen(&system));
+    });
+    let result = result.lock().unwrap().clone();
+    result
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn all_choices_explored() {
+    let goal = |v: StateView| {
+        if chosen(&v.system()).len() == 2 {
+            Ok(())
+        } else {
+            Err("not chosen".to_string())
+        }
+    };
+    let expected: BTreeSet<_> = (0..2)
+        .flat_map(|first| (0..3).map(move |second| vec![first, second]))
+        .collect();
+    for searcher in 0..4 {
+        let cfg = SearchConfigBuilder::no_faults().build();
+        let mut checker = mc::ModelChecker::new_with_build(build);
+        let invariant = |_| Ok(());
+        let prune = |_| false;
+        let log = match searcher {
+            0 => checker.collect(invariant, prune, goal, mc::BfsSearcher::new(cfg)),
+            1 => checker.collect(invariant, prune, goal, mc::DfsSearcher::new(cfg)),
+            2 => checker.collect(invariant, prune, goal, mc::ParallelSearcher::new(cfg, 2)),
+            _ => {
+                let cfg = SearchConfigBuilder::no_faults().max_depth(10).build();
+                let searcher = mc::RandomWalkSearcher::new(cfg, 123, 100);
+                checker.collect(invariant, prune, goal, searcher)
+            }
+        }
+        .unwrap();
+        assert_eq!(collected(&checker), expected);
+        if searcher < 3 {
+            assert_eq!(checker.states_count(), 6);
+            assert_eq!(log.steps["Choose"], 5);
+        }
+    }
+}
+
+#[test]
+fn all_choices_followed() {
+    let expected: BTreeSet<_> = (0..2)
+        .flat_map(|first| (0..3).map(move |second| vec![first, second]))
+        .collect();
+    let cfg = SearchConfigBuilder::no_faults().build();
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    let schedule = [ScheduleConstraint::timer("n:chooser")];
+    let log = checker.follow(&schedule, &cfg, |_| Ok(())).unwrap();
+    assert_eq!(collected(&checker), expected);
+    assert_eq!(checker.states_count(), 6);
+    assert_eq!(log.steps["Choose"], 5);
+}
+
+#[test]
+fn violation_replayed() {
+    let invariant = |v: StateView| {
+        if chosen(&v.system()) == [1, 2] {
+            Err("bad choice".to_string())
+        } else {
+            Ok(())
+        }
+    };
+    let path = std::env::temp_dir().join(format!("dsbuild-mc-choose-{}.json", std::process::id()));
+    let cfg = SearchConfigBuilder::no_faults().build();
+    let err = mc::ModelChecker::new_with_build(build)
+        .check(
+            invariant,
+            |_| false,
+            |v: StateView| match chosen(&v.system()).len() {
+                2 => Ok(()),
+                _ => Err("not chosen".into()),
+            },
+            mc::DfsSearcher::new(cfg),
+        )
+        .unwrap_err();
+    let SearchErrorKind::InvariantViolation(violation) = &err.kind else {
+        panic!("unexpected error: {}", err);
+    };
+    assert!(violation.trace.to_string().contains("choices [1, 2]"));
+    assert!(violation.log.to_string().contains("chose 2 of 3"));
+
+    err.save_trace(&path).unwrap();
+    let checker = mc::ModelChecker::new_with_build(build);
+    let replayed = checker.replay(&path, invariant).unwrap();
+    assert!(matches!(
+        replayed,
+        Some(SearchErrorKind::InvariantViolation(_))
+    ));
+    std::fs::remove_file(path).unwrap();
+}
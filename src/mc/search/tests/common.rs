@@ -0,0 +1,126 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    choose,
+    mc::StateView,
+    model::{node::Node, SystemHandle},
+    sleep, spawn, Address, HashType, Process,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Ticks until the limit, ticks are counted modulo provided value.
+pub struct Ticker {
+    ticks: Rc<RefCell<u64>>,
+    limit: Option<u64>,
+    modulo: u64,
+}
+
+impl Process for Ticker {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, _content: String) {
+        let ticks = self.ticks.clone();
+        let limit = self.limit;
+        let modulo = self.modulo;
+        spawn(async move {
+            for _ in 0..limit.unwrap_or(u64::MAX) {
+                sleep(Duration::from_millis(100)).await;
+                let next = (*ticks.borrow() + 1) % modulo;
+                *ticks.borrow_mut() = next;
+            }
+        });
+    }
+
+    fn hash(&self) -> HashType {
+        *self.ticks.borrow() as HashType
+    }
+}
+
+/// Adds nodes with the started tickers, which tick until the limit or forever.
+pub fn add_tickers(system: &SystemHandle, nodes: &[&str], limit: Option<u64>) {
+    for name in nodes {
+        add_ticker(system, name, limit, u64::MAX);
+    }
+}
+
+/// Adds node with the started ticker, ticks of which are counted modulo provided value.
+pub fn add_ticker(system: &SystemHandle, node: &str, limit: Option<u64>, modulo: u64) {
+    let mut n = Node::new(node);
+    let ticker = Ticker {
+        ticks: Default::default(),
+        limit,
+        modulo,
+    };
+    n.add_proc("ticker", ticker).unwrap();
+    system.add_node(n).unwrap();
+    system
+        .send_local(&Address::new(node, "ticker"), "start")
+        .unwrap();
+}
+
+pub fn ticks(v: &StateView, node: &str) -> u64 {
+    let ticker = v
+        .system()
+        .proc_state::<Ticker>(Address::new(node, "ticker"))
+        .unwrap();
+    let ticks = *ticker.borrow().ticks.borrow();
+    ticks
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Flips the coin after every timer.
+pub struct Coin {
+    flips: Rc<RefCell<Vec<usize>>>,
+    count: usize,
+}
+
+impl Process for Coin {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, _content: String) {
+        let flips = self.flips.clone();
+        let count = self.count;
+        spawn(async move {
+            for _ in 0..count {
+                sleep(Duration::from_millis(100)).await;
+                let flip = choose(2);
+                flips.borrow_mut().push(flip);
+            }
+        });
+    }
+
+    fn hash(&self) -> HashType {
+        self.flips
+            .borrow()
+            .iter()
+            .fold(1, |h, f| h * 2 + *f as HashType)
+    }
+}
+
+/// Adds node with the coin, which is flipped provided number of times.
+pub fn add_coin(system: &SystemHandle, count: usize) {
+    let mut node = Node::new("n");
+    let coin = Coin {
+        flips: Default::default(),
+        count,
+    };
+    node.add_proc("coin", coin).unwrap();
+    system.add_node(node).unwrap();
+    system
+        .send_local(&Address::new("n", "coin"), "flip")
+        .unwrap();
+}
+
+pub fn flips(system: &SystemHandle) -> Vec<usize> {
+    let coin = system
+        .proc_state::<Coin>(Address::new("n", "coin"))
+        .unwrap();
+    let flips = coin.borrow().flips.borrow().clone();
+    flips
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use crate::{
+    mc::{
+        self,
+        error::{SearchError, SearchErrorKind},
+        search::state::SearchState,
+        FairEvent, Fairness, SearchConfigBuilder, StateView,
+    },
+    model::{net::send_message, node::Node, SystemHandle},
+    sleep, spawn, Address, HashType, Process,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Resends message to the receiver forever.
+struct Sender {}
+
+impl Process for Sender {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, content: String) {
+        spawn(async move {
+            loop {
+                send_message(&receiver(), content.clone());
+                sleep(Duration::from_millis(300)).await;
+            }
+        });
+    }
+
+    fn hash(&self) -> HashType {
+        0
+    }
+}
+
+struct Receiver {
+    received: bool,
+}
+
+impl Process for Receiver {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        self.received = true;
+    }
+
+    fn on_local_message(&mut self, _content: String) {
+        unreachable!()
+    }
+
+    fn hash(&self) -> HashType {
+        self.received as HashType
+    }
+}
+
+fn receiver() -> Address {
+    Address::new("receiver", "receiver")
+}
+
+fn build(system: SystemHandle) {
+    let mut sender = Node::new("sender");
+    sender.add_proc("sender", Sender {}).unwrap();
+    system.add_node(sender).unwrap();
+    let mut receiver = Node::new("receiver");
+    receiver
+        .add_proc("receiver", Receiver { received: false })
+        .unwrap();
+    system.add_node(receiver).unwrap();
+    system
+        .send_local(&Address::new("sender", "sender"), "ping")
+        .unwrap();
+}
+
+fn received(v: StateView) -> Result<(), String> {
+    let receiver = v.system().proc_state::<Receiver>(receiver()).unwrap();
+    if receiver.borrow().received {
+        Ok(())
+    } else {
+        Err("message is not received".into())
+    }
+}
+
+fn check(fairness: Fairness, dfs: bool) -> Result<mc::SearchLog, SearchError> {
+    let cfg = SearchConfigBuilder::no_faults().fairness(fairness).build();
+    let checker = mc::ModelChecker::new_with_build(build);
+    if dfs {
+        checker.check(|_| Ok(()), |_| false, received, mc::DfsSearcher::new(cfg))
+    } else {
+        checker.check(|_| Ok(()), |_| false, received, mc::BfsSearcher::new(cfg))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn unfair_cycle_is_ignored() {
+    for dfs in [false, true] {
+        // message is delivered if it is not always dropped
+        let fair = Fairness::strong().events(FairEvent::Udp);
+        check(fair, dfs).unwrap();
+
+        let fair = Fairness::strong().process(receiver());
+        check(fair, dfs).unwrap();
+    }
+}
+
+#[test]
+fn fair_cycle() {
+    for dfs in [false, true] {
+        // delivery is not enabled after the drop, so the weak fairness
+        // does not exclude the cycle, in which messages are always dropped
+        let fair = Fairness::weak().events(FairEvent::Udp);
+        let err = check(fair, dfs).unwrap_err();
+        let SearchErrorKind::FairCycle(cycle) = err.kind else {
+            panic!("unexpected error: {}", err);
+        };
+        assert!(cycle.cycle.depth() > 0);
+
+        // the cycle leads back to the first state
+        let mut trace = cycle.prefix.clone();
+        (0..cycle.cycle.depth()).for_each(|i| trace.add_step(cycle.cycle.step(i).clone()));
+        let state = SearchState::from_trace(&trace).unwrap();
+        assert_eq!(state.system.handle().hash(), cycle.hash);
+    }
+}
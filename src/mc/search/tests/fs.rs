@@ -4,13 +4,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     mc::search::{gen::Generator, state::SearchState, step::StateTraceStep},
-    mc::SearchConfig,
+    mc::{SearchConfig, SearchConfigBuilder},
     model::event::driver::EventDriver,
     model::fs::file::File,
+    model::log::LogEntry,
     model::node::Node,
     model::system::System,
     model::SystemHandle,
-    send_local, spawn, Address, HashType, Process,
+    send_local, spawn, Address, FsError, HashType, Process,
 };
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -113,7 +114,11 @@ fn basic() {
     let gen = Rc::new(RefCell::new(Generator::new()));
     let system = System::new_default_net(&(gen.clone() as Rc<RefCell<dyn EventDriver>>));
     build_system(system.handle());
-    let mut state = SearchState { system, gen };
+    let mut state = SearchState {
+        system,
+        gen,
+        depth: 0,
+    };
 
     let cfg = SearchConfig::no_faults_no_drops();
 
@@ -195,3 +200,128 @@ fn basic() {
     assert_eq!(msgs.len(), 2);
     assert_eq!(msgs[1], "ello\n");
 }
+
+////////////////////////////////////////////////////////////////////////////////
+
+struct Writer {}
+
+impl Process for Writer {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, content: String) {
+        spawn(async move {
+            let mut file = match File::open("f") {
+                Ok(file) => file,
+                Err(_) => File::create("f").unwrap(),
+            };
+            let result = file.write(content.as_bytes(), 0).await;
+            match result {
+                Ok(_) => send_local("ok"),
+                Err(err) => send_local(err.to_string()),
+            }
+        });
+    }
+
+    fn hash(&self) -> HashType {
+        0
+    }
+}
+
+fn build_writer_state() -> SearchState {
+    let gen = Rc::new(RefCell::new(Generator::new()));
+    let system = System::new_default_net(&(gen.clone() as Rc<RefCell<dyn EventDriver>>));
+    let s = system.handle();
+    let mut node = Node::new("n");
+    node.add_proc("p", Writer {}).unwrap();
+    s.add_node(node).unwrap();
+    s.setup_fs(
+        "n",
+        Duration::from_millis(20),
+        Duration::from_millis(100),
+        100,
+    )
+    .unwrap();
+    SearchState {
+        system,
+        gen,
+        depth: 0,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn io_failure() {
+    let mut state = build_writer_state();
+    let cfg = SearchConfigBuilder::no_faults().max_disk_faults(1).build();
+
+    state
+        .system
+        .handle()
+        .send_local(&"n:p".into(), "hello")
+        .unwrap();
+
+    let steps = state.steps(&cfg);
+    assert_eq!(steps.len(), 3);
+    assert!(matches!(steps[0], StateTraceStep::SelectFsEvent(..)));
+    assert!(matches!(steps[1], StateTraceStep::FailFsEvent(..)));
+    assert!(matches!(steps[2], StateTraceStep::CrashFs(0)));
+
+    // fail write request
+    steps[1].apply(&mut state).unwrap();
+    let msgs = state.system.handle().read_locals("n", "p").unwrap();
+    assert_eq!(msgs, vec![FsError::IoFailure.to_string()]);
+    assert_eq!(state.system.handle().stat().disk_faults, 1);
+
+    // budget is exhausted
+    state
+        .system
+        .handle()
+        .send_local(&"n:p".into(), "hello")
+        .unwrap();
+    let steps = state.steps(&cfg);
+    assert_eq!(steps.len(), 1);
+    assert!(matches!(steps[0], StateTraceStep::SelectFsEvent(..)));
+
+    steps[0].apply(&mut state).unwrap();
+    let msgs = state.system.handle().read_locals("n", "p").unwrap();
+    assert_eq!(msgs.last().unwrap(), "ok");
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn fs_crash() {
+    let mut state = build_writer_state();
+    let cfg = SearchConfigBuilder::no_faults().max_disk_faults(2).build();
+
+    state
+        .system
+        .handle()
+        .send_local(&"n:p".into(), "hello")
+        .unwrap();
+
+    let steps = state.steps(&cfg);
+    let crash = steps
+        .iter()
+        .find(|s| matches!(s, StateTraceStep::CrashFs(0)))
+        .unwrap();
+
+    // pending request fails on crash
+    crash.apply(&mut state).unwrap();
+    let msgs = state.system.handle().read_locals("n", "p").unwrap();
+    assert_eq!(msgs, vec![FsError::StorageNotAvailable.to_string()]);
+    assert_eq!(state.system.handle().stat().disk_faults, 1);
+    assert!(state
+        .system
+        .handle()
+        .log()
+        .iter()
+        .any(|e| matches!(e, LogEntry::FsCrashed(..))));
+
+    // no file system to crash or fail
+    let steps = state.steps(&cfg);
+    assert!(steps.is_empty());
+}
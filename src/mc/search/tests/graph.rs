@@ -0,0 +1,115 @@
+use crate::{
+    mc::{
+        self,
+        search::tests::common::{add_tickers, ticks},
+        GraphRecorder, NodeMark, SearchConfigBuilder, StateView,
+    },
+    model::SystemHandle,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+fn build(system: SystemHandle) {
+    add_tickers(&system, &["a", "b"], Some(3));
+}
+
+fn prune(v: StateView) -> bool {
+    ticks(&v, "a") == 2 && ticks(&v, "b") == 1
+}
+
+fn goal(v: StateView) -> Result<(), String> {
+    if ticks(&v, "a") == 3 && ticks(&v, "b") == 3 {
+        Ok(())
+    } else {
+        Err("not finished".into())
+    }
+}
+
+fn record(dfs: bool, collect: bool) -> GraphRecorder {
+    let cfg = SearchConfigBuilder::no_faults().build();
+    let recorder = GraphRecorder::new();
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    let invariant = |_| Ok(());
+    let checked = match (dfs, collect) {
+        (false, false) => {
+            let searcher = mc::BfsSearcher::new(cfg).record_graph(recorder.clone());
+            checker.check(invariant, prune, goal, searcher).map(|_| ())
+        }
+        (true, false) => {
+            let searcher = mc::DfsSearcher::new(cfg).record_graph(recorder.clone());
+            checker.check(invariant, prune, goal, searcher).map(|_| ())
+        }
+        (false, true) => {
+            let searcher = mc::BfsSearcher::new(cfg).record_graph(recorder.clone());
+            checker
+                .collect(invariant, prune, goal, searcher)
+                .map(|_| ())
+        }
+        (true, true) => {
+            let searcher = mc::DfsSearcher::new(cfg).record_graph(recorder.clone());
+            checker
+                .collect(invariant, prune, goal, searcher)
+                .map(|_| ())
+        }
+    };
+    checked.unwrap();
+    recorder
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn explored_graph() {
+    let bfs = record(false, false);
+    assert_eq!(bfs.marked(NodeMark::Initial).len(), 1);
+    assert_eq!(bfs.marked(NodeMark::Goal).len(), 1);
+    assert_eq!(bfs.marked(NodeMark::Pruned).len(), 1);
+    assert!(bfs.marked(NodeMark::Violation).is_empty());
+    assert!(bfs.edges_count() >= bfs.nodes_count() - 1);
+
+    // the same states and transitions are explored in any order,
+    // but events can be numbered differently
+    for (dfs, collect) in [(true, false), (false, true), (true, true)] {
+        let other = record(dfs, collect);
+        assert_eq!(other.nodes_count(), bfs.nodes_count());
+        assert_eq!(other.edges_count(), bfs.edges_count());
+        for mark in [NodeMark::Initial, NodeMark::Goal, NodeMark::Pruned] {
+            assert_eq!(other.marked(mark), bfs.marked(mark));
+        }
+    }
+}
+
+#[test]
+fn export() {
+    let recorder = record(false, false);
+
+    let dot = recorder.to_dot();
+    assert!(dot.starts_with("digraph states {"));
+    assert_eq!(
+        dot.lines().count(),
+        recorder.nodes_count() + recorder.edges_count() + 2
+    );
+    let goal = format!("{:032x}", recorder.marked(NodeMark::Goal)[0]);
+    assert!(dot.contains(&format!(
+        "\"{}\" [label=\"{}\\nGoal\", color=green];",
+        goal, goal
+    )));
+    assert!(dot.contains("Timer"));
+
+    let json: serde_json::Value = serde_json::from_str(&recorder.to_json()).unwrap();
+    assert_eq!(
+        json["nodes"].as_array().unwrap().len(),
+        recorder.nodes_count()
+    );
+    assert_eq!(
+        json["edges"].as_array().unwrap().len(),
+        recorder.edges_count()
+    );
+    let goal_node = json["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|n| n["hash"] == goal)
+        .unwrap();
+    assert_eq!(goal_node["marks"], serde_json::json!(["goal"]));
+}
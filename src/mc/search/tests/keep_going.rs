@@ -0,0 +1,134 @@
+use crate::{
+    mc::{
+        self,
+        error::{SearchError, SearchErrorKind},
+        search::tests::common::{add_tickers, ticks},
+        SearchConfigBuilder, StateView,
+    },
+    model::SystemHandle,
+    HashType,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+fn build(system: SystemHandle) {
+    add_tickers(&system, &["a", "b"], Some(3));
+}
+
+fn check(
+    cfg: SearchConfigBuilder,
+    invariant: impl mc::InvariantFn,
+    dfs: bool,
+) -> Result<mc::SearchLog, SearchError> {
+    let cfg = cfg.build();
+    let checker = mc::ModelChecker::new_with_build(build);
+    let goal = |_| Err("never".into());
+    if dfs {
+        checker.check(invariant, |_| false, goal, mc::DfsSearcher::new(cfg))
+    } else {
+        checker.check(invariant, |_| false, goal, mc::BfsSearcher::new(cfg))
+    }
+}
+
+fn ahead(v: StateView) -> Result<(), String> {
+    match (ticks(&v, "a"), ticks(&v, "b")) {
+        (2, 1) => Err("a is ahead".into()),
+        (1, 2) => Err("b is ahead".into()),
+        _ => Ok(()),
+    }
+}
+
+fn reports(err: SearchError) -> Vec<String> {
+    let SearchErrorKind::InvariantViolations(violations) = err.kind else {
+        panic!("unexpected error: {}", err);
+    };
+    let mut reports: Vec<String> = violations.into_iter().map(|v| v.report).collect();
+    reports.sort();
+    reports
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn distinct_violations() {
+    for dfs in [false, true] {
+        let cfg = SearchConfigBuilder::no_faults().keep_going(10);
+        let err = check(cfg, ahead, dfs).unwrap_err();
+        assert!(err.log.visited_total > 3);
+        assert_eq!(reports(err), vec!["a is ahead", "b is ahead"]);
+    }
+}
+
+#[test]
+fn same_violation_in_same_state() {
+    // state is reached by both orders of the first ticks
+    let both = |v: StateView| match (ticks(&v, "a"), ticks(&v, "b")) {
+        (1, 1) => Err("both ticked".to_string()),
+        _ => Ok(()),
+    };
+    for dfs in [false, true] {
+        let cfg = SearchConfigBuilder::no_faults().keep_going(10);
+        let err = check(cfg, both, dfs).unwrap_err();
+        assert_eq!(reports(err), vec!["both ticked"]);
+    }
+}
+
+#[test]
+fn stop_at_max_violations() {
+    for dfs in [false, true] {
+        let cfg = SearchConfigBuilder::no_faults().keep_going(1);
+        let err = check(cfg, ahead, dfs).unwrap_err();
+        assert_eq!(reports(err).len(), 1);
+
+        let err = check(SearchConfigBuilder::no_faults(), ahead, dfs).unwrap_err();
+        assert!(matches!(err.kind, SearchErrorKind::InvariantViolation(..)));
+    }
+}
+
+#[test]
+fn same_violation_in_same_abstract_state() {
+    let unequal = |v: StateView| {
+        if ticks(&v, "a") == ticks(&v, "b") {
+            Ok(())
+        } else {
+            Err("unequal".to_string())
+        }
+    };
+    let total = |v: StateView| (ticks(&v, "a") + ticks(&v, "b")) as HashType;
+    for dfs in [false, true] {
+        let cfg = SearchConfigBuilder::no_faults().keep_going(10);
+        let err = check(cfg, unequal, dfs).unwrap_err();
+        assert_eq!(reports(err).len(), 2);
+
+        let cfg = SearchConfigBuilder::no_faults().keep_going(10).build();
+        let checker = mc::ModelChecker::new_with_build(build);
+        let goal = |_| Err("never".into());
+        let err = if dfs {
+            let searcher = mc::DfsSearcher::new(cfg);
+            checker.check_with_abstraction(unequal, |_| false, goal, total, searcher)
+        } else {
+            let searcher = mc::BfsSearcher::new(cfg);
+            checker.check_with_abstraction(unequal, |_| false, goal, total, searcher)
+        }
+        .unwrap_err();
+        assert_eq!(reports(err).len(), 1);
+    }
+}
+
+#[test]
+fn violations_kept_on_liveness_violation() {
+    // the terminal state is reached if "a" ticks first
+    let late = |v: StateView| match (ticks(&v, "a"), ticks(&v, "b")) {
+        (0, 1) => Err("b is first".to_string()),
+        _ => Ok(()),
+    };
+    for dfs in [false, true] {
+        let cfg = SearchConfigBuilder::no_faults().keep_going(10);
+        let err = check(cfg, late, dfs).unwrap_err();
+        assert_eq!(reports(err), vec!["b is first"]);
+
+        let cfg = SearchConfigBuilder::no_faults().keep_going(10);
+        let err = check(cfg, |_| Ok(()), dfs).unwrap_err();
+        assert!(matches!(err.kind, SearchErrorKind::LivenessViolation(..)));
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod common;
+
 #[cfg(test)]
 mod fs;
 
@@ -9,3 +12,51 @@ mod rpc;
 
 #[cfg(test)]
 mod node;
+
+#[cfg(test)]
+mod partition;
+
+#[cfg(test)]
+mod shrink;
+
+#[cfg(test)]
+mod budget;
+
+#[cfg(test)]
+mod property;
+
+#[cfg(test)]
+mod fairness;
+
+#[cfg(test)]
+mod transition;
+
+#[cfg(test)]
+mod keep_going;
+
+#[cfg(test)]
+mod graph;
+
+#[cfg(test)]
+mod choose;
+
+#[cfg(test)]
+mod random;
+
+#[cfg(test)]
+mod workload;
+
+#[cfg(test)]
+mod schedule;
+
+#[cfg(test)]
+mod best_first;
+
+#[cfg(test)]
+mod abstraction;
+
+#[cfg(test)]
+mod visited;
+
+#[cfg(test)]
+mod por;
@@ -3,9 +3,10 @@ use std::{cell::RefCell, rc::Rc, time::Duration};
 use crate::{
     mc::{
         search::{gen::Generator, state::SearchState, step::StateTraceStep},
-        SearchConfig,
+        SearchConfig, SearchConfigBuilder,
     },
     model::{event::driver::EventDriver, net::Config as NetConfig, node::Node, system::System},
+    send_local, Address, HashType, Process,
 };
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -19,7 +20,11 @@ fn node_shutdown() {
     system.handle().add_node(Node::new("node1")).unwrap();
     system.handle().add_node(Node::new("node2")).unwrap();
 
-    let mut state = SearchState { system, gen };
+    let mut state = SearchState {
+        system,
+        gen,
+        depth: 0,
+    };
 
     let steps = state.steps(&SearchConfig::with_node_shutdown_only(2));
     assert_eq!(steps.len(), 2);
@@ -38,3 +43,75 @@ fn node_shutdown() {
     let steps = state.steps(&SearchConfig::with_node_shutdown_only(1));
     assert!(steps.is_empty());
 }
+
+////////////////////////////////////////////////////////////////////////////////
+
+struct Echo {}
+
+impl Process for Echo {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, content: String) {
+        send_local(content);
+    }
+
+    fn hash(&self) -> HashType {
+        0
+    }
+}
+
+#[test]
+fn node_restart() {
+    let gen = Rc::new(RefCell::new(Generator::new()));
+    let net = NetConfig::new(Duration::from_millis(100), Duration::from_millis(200)).unwrap();
+
+    let system = System::new(&net, &(gen.clone() as Rc<RefCell<dyn EventDriver>>));
+    system
+        .handle()
+        .add_node_with_role(Node::new("node1"), "echo")
+        .unwrap();
+    system.handle().add_node(Node::new("node2")).unwrap();
+    system.handle().register_process_factory("echo", |s, node| {
+        s.add_proc_on_node(node, "echo", Echo {}).unwrap();
+        s.send_local(&Address::new(node, "echo"), "init").unwrap();
+    });
+
+    let mut state = SearchState {
+        system,
+        gen,
+        depth: 0,
+    };
+
+    let cfg = SearchConfigBuilder::no_faults()
+        .max_msg_drops(0)
+        .max_node_shutdown(2)
+        .max_node_restarts(1)
+        .build();
+
+    // shutdown both nodes
+    StateTraceStep::ShutdownNode(0).apply(&mut state).unwrap();
+    StateTraceStep::ShutdownNode(1).apply(&mut state).unwrap();
+
+    // only node with registered factory can be restarted
+    let steps = state.steps(&cfg);
+    assert_eq!(steps.len(), 1);
+    assert!(matches!(steps[0], StateTraceStep::RestartNode(0)));
+
+    steps[0].apply(&mut state).unwrap();
+
+    let handle = state.system.handle();
+    assert!(handle.node_available_index(0));
+    assert!(handle.proc(Address::new("node1", "echo")).is_some());
+    assert_eq!(
+        handle.read_locals("node1", "echo").unwrap(),
+        vec!["init".to_string()]
+    );
+    assert_eq!(handle.stat().nodes_restarted, 1);
+
+    // budget is exhausted
+    StateTraceStep::ShutdownNode(0).apply(&mut state).unwrap();
+    let steps = state.steps(&cfg);
+    assert!(steps.is_empty());
+}
@@ -0,0 +1,89 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    mc::{
+        search::{gen::Generator, state::SearchState, step::StateTraceStep},
+        SearchConfigBuilder,
+    },
+    model::{event::driver::EventDriver, net::Config as NetConfig, node::Node, system::System},
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn partition_and_heal() {
+    let gen = Rc::new(RefCell::new(Generator::new()));
+    let net = NetConfig::new(Duration::from_millis(100), Duration::from_millis(200)).unwrap();
+
+    let system = System::new(&net, &(gen.clone() as Rc<RefCell<dyn EventDriver>>));
+    let handle = system.handle();
+    handle
+        .add_node_with_role(Node::new("node1"), "replica")
+        .unwrap();
+    handle
+        .add_node_with_role(Node::new("node2"), "replica")
+        .unwrap();
+    handle
+        .add_node_with_role(Node::new("node3"), "client")
+        .unwrap();
+
+    let mut state = SearchState {
+        system,
+        gen,
+        depth: 0,
+    };
+
+    let cfg = SearchConfigBuilder::no_faults()
+        .max_msg_drops(0)
+        .max_partitions(1)
+        .build();
+
+    // replica | replica, client and replica, replica | client
+    let steps = state.steps(&cfg);
+    assert_eq!(steps.len(), 2);
+    assert!(matches!(&steps[0], StateTraceStep::Partition(nodes) if nodes == &[0]));
+    assert!(matches!(&steps[1], StateTraceStep::Partition(nodes) if nodes == &[0, 1]));
+
+    steps[1].apply(&mut state).unwrap();
+    assert_eq!(
+        state.system.handle().network().groups(),
+        vec![vec!["node1".to_string(), "node2".to_string()]]
+    );
+
+    let steps = state.steps(&cfg);
+    assert_eq!(steps.len(), 1);
+    assert!(matches!(steps[0], StateTraceStep::Heal));
+
+    steps[0].apply(&mut state).unwrap();
+    assert!(!state.system.handle().network().partitioned());
+
+    // budget is exhausted
+    let steps = state.steps(&cfg);
+    assert!(steps.is_empty());
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn many_nodes_with_same_role() {
+    let gen = Rc::new(RefCell::new(Generator::new()));
+    let net = NetConfig::new(Duration::from_millis(100), Duration::from_millis(200)).unwrap();
+
+    let system = System::new(&net, &(gen as Rc<RefCell<dyn EventDriver>>));
+    let handle = system.handle();
+    for i in 0..100 {
+        handle
+            .add_node_with_role(Node::new(format!("node{:03}", i)), "replica")
+            .unwrap();
+    }
+    handle
+        .add_node_with_role(Node::new("node100"), "client")
+        .unwrap();
+
+    // number of replicas separated with or without the client
+    let splits = handle.partitions_index();
+    assert_eq!(splits.len(), 100);
+    assert!(splits
+        .iter()
+        .all(|nodes| !nodes.is_empty() && nodes.len() < 101));
+}
@@ -0,0 +1,132 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    mc::{self, SearchConfigBuilder, StateView},
+    model::{net::send_message, node::Node, SystemHandle},
+    sleep, spawn, Address, HashType, Process,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Starts together with the delivery of the message
+/// and toggles the phase every millisecond forever.
+struct Toggler {
+    phase: Rc<RefCell<u64>>,
+}
+
+impl Process for Toggler {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, _content: String) {
+        let phase = self.phase.clone();
+        spawn(async move {
+            sleep(Duration::from_millis(99)).await;
+            *phase.borrow_mut() = 1;
+            loop {
+                sleep(Duration::from_millis(1)).await;
+                let next = if *phase.borrow() == 2 { 3 } else { 2 };
+                *phase.borrow_mut() = next;
+            }
+        });
+    }
+
+    fn hash(&self) -> HashType {
+        *self.phase.borrow() as HashType
+    }
+}
+
+/// Sends one message to the receiver and records the received ones.
+#[derive(Default)]
+struct Messenger {
+    received: usize,
+}
+
+impl Process for Messenger {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        self.received += 1;
+    }
+
+    fn on_local_message(&mut self, content: String) {
+        send_message(&Address::new(content, "messenger"), "hello");
+    }
+
+    fn hash(&self) -> HashType {
+        self.received as HashType
+    }
+}
+
+fn build(system: SystemHandle) {
+    for name in ["sender", "receiver"] {
+        let mut node = Node::new(name);
+        node.add_proc("messenger", Messenger::default()).unwrap();
+        system.add_node(node).unwrap();
+    }
+    let mut node = Node::new("toggler");
+    let toggler = Toggler {
+        phase: Default::default(),
+    };
+    node.add_proc("toggler", toggler).unwrap();
+    system.add_node(node).unwrap();
+
+    system
+        .send_local(&Address::new("sender", "messenger"), "receiver")
+        .unwrap();
+    system
+        .send_local(&Address::new("toggler", "toggler"), "start")
+        .unwrap();
+}
+
+fn received(v: &StateView) -> usize {
+    let receiver = v
+        .system()
+        .proc_state::<Messenger>(Address::new("receiver", "messenger"))
+        .unwrap();
+    let received = receiver.borrow().received;
+    received
+}
+
+fn delivered(v: StateView) -> Result<(), String> {
+    match received(&v) {
+        1 => Ok(()),
+        _ => Err("not delivered".into()),
+    }
+}
+
+/// Ignores the time of the pending events,
+/// so the toggling makes the cycle of the states.
+fn processes(v: StateView) -> HashType {
+    let toggler = v
+        .system()
+        .proc_state::<Toggler>(Address::new("toggler", "toggler"))
+        .unwrap();
+    let phase = toggler.borrow().hash();
+    phase * 2 + received(&v) as HashType
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn cycle_with_sleeping_step() {
+    let cfg = SearchConfigBuilder::no_faults()
+        .max_msg_drops(0)
+        .partial_order_reduction(true)
+        .max_visited_states(1000)
+        .build();
+    let log = mc::ModelChecker::new_with_build(build)
+        .check_with_abstraction(
+            |_| Ok(()),
+            |_| false,
+            delivered,
+            processes,
+            mc::DfsSearcher::new(cfg),
+        )
+        .unwrap();
+
+    // toggling after the message is delivered is not explored,
+    // and the cycle of the toggling states with the sleeping delivery
+    // is explored only once
+    assert_eq!(log.visited_unique, 5);
+    assert_eq!(log.visited_total, 6);
+}
@@ -0,0 +1,158 @@
+use crate::{
+    mc::{
+        self,
+        error::{SearchError, SearchErrorKind},
+        search::tests::common::{self, add_ticker},
+        Predicate, Property, SearchConfigBuilder, StateView,
+    },
+    model::SystemHandle,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+fn make_build(limit: Option<u64>, modulo: u64) -> impl mc::ApplyFn {
+    move |system: SystemHandle| add_ticker(&system, "node", limit, modulo)
+}
+
+fn ticks(v: &StateView) -> u64 {
+    common::ticks(v, "node")
+}
+
+fn ticks_eq(n: u64) -> Predicate {
+    Predicate::new(move |v| ticks(v) == n)
+}
+
+fn check(
+    build: impl mc::ApplyFn,
+    property: Property,
+    goal: impl mc::GoalFn,
+    dfs: bool,
+) -> Result<mc::SearchLog, SearchError> {
+    let cfg = SearchConfigBuilder::no_faults().property(property).build();
+    let checker = mc::ModelChecker::new_with_build(build);
+    if dfs {
+        let searcher = mc::DfsSearcher::new(cfg);
+        checker.check(|_| Ok(()), |_| false, goal, searcher)
+    } else {
+        let searcher = mc::BfsSearcher::new(cfg);
+        checker.check(|_| Ok(()), |_| false, goal, searcher)
+    }
+}
+
+fn violated_property(err: SearchError) -> (String, Option<usize>) {
+    match err.kind {
+        SearchErrorKind::PropertyViolation(v) => (v.property, v.cycle_start),
+        _ => panic!("unexpected error: {}", err),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn always() {
+    let build = make_build(Some(5), u64::MAX);
+    let goal = |v: StateView| {
+        if ticks(&v) == 5 {
+            Ok(())
+        } else {
+            Err("not finished".into())
+        }
+    };
+
+    for dfs in [false, true] {
+        // tick 2 goes right after tick 1
+        let property = Property::always("order", ticks_eq(2).implies(ticks_eq(1).previous()));
+        check(build.clone(), property, goal, dfs).unwrap();
+
+        // tick 0 does not come back after tick 1
+        let property =
+            Property::always("no reset", ticks_eq(0).implies(ticks_eq(0).historically()));
+        check(build.clone(), property, goal, dfs).unwrap();
+
+        let property = Property::always("less than 3", ticks_eq(3).once().not());
+        let err = check(build.clone(), property, goal, dfs).unwrap_err();
+        assert_eq!(violated_property(err), ("less than 3".to_string(), None));
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn eventually() {
+    let build = make_build(Some(5), u64::MAX);
+    let goal = |_| Err("never".into());
+
+    for dfs in [false, true] {
+        let property = Property::eventually("reach 10", ticks_eq(10));
+        let err = check(build.clone(), property, goal, dfs).unwrap_err();
+        let trace = err.kind.trace().unwrap().clone();
+        let (property, cycle) = violated_property(err);
+        assert_eq!(property, "reach 10");
+        assert!(cycle.is_none());
+        assert_eq!(trace.depth(), 6);
+
+        // liveness violation is reported then
+        let property = Property::eventually("reach 3", ticks_eq(3));
+        let err = check(build.clone(), property, goal, dfs).unwrap_err();
+        assert!(matches!(err.kind, SearchErrorKind::LivenessViolation(..)));
+
+        // obligation is not satisfied when the goal is achieved
+        let property = Property::eventually("reach 4", ticks_eq(4));
+        let goal = |v: StateView| {
+            if ticks(&v) == 3 {
+                Ok(())
+            } else {
+                Err("not finished".into())
+            }
+        };
+        let err = check(build.clone(), property, goal, dfs).unwrap_err();
+        let trace = err.kind.trace().unwrap().clone();
+        assert_eq!(violated_property(err), ("reach 4".to_string(), None));
+        assert_eq!(trace.depth(), 4);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn leads_to_lasso() {
+    // ticks forever: 0, 1, 0, 1...
+    let build = make_build(None, 2);
+    let goal = |_| Err("never".into());
+
+    for dfs in [false, true] {
+        let property = Property::leads_to("one leads to zero", ticks_eq(1), ticks_eq(0));
+        let err = check(build.clone(), property, goal, dfs).unwrap_err();
+        assert!(matches!(err.kind, SearchErrorKind::Cycled(..)));
+
+        let property = Property::leads_to("one leads to two", ticks_eq(1), ticks_eq(2));
+        let err = check(build.clone(), property, goal, dfs).unwrap_err();
+        let (property, cycle) = violated_property(err);
+        assert_eq!(property, "one leads to two");
+        assert!(cycle.is_some());
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn eventually_interleaved_cycle() {
+    // two tickers tick in turns forever: 0, 1, 0... and 0, 1, 2, 0...
+    let build = |system: SystemHandle| {
+        add_ticker(&system, "a", None, 2);
+        add_ticker(&system, "b", None, 3);
+    };
+    let goal = |_| Err("never".into());
+    let state_eq =
+        |a, b| Predicate::new(move |v| common::ticks(v, "a") == a && common::ticks(v, "b") == b);
+
+    for dfs in [false, true] {
+        // some order of the ticks avoids the state forever, and the states of the cycle
+        // are first explored by the other paths
+        let property = Property::eventually("reach (0, 2)", state_eq(0, 2));
+        let err = check(build, property, goal, dfs).unwrap_err();
+        let (property, cycle) = violated_property(err);
+        assert_eq!(property, "reach (0, 2)");
+        assert!(cycle.is_some());
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    mc::{self, SearchConfigBuilder, StateView},
+    model::{node::Node, SystemHandle},
+    random, random_range, sleep, spawn, Address, HashType, Process,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Makes random values after the timer is fired.
+struct Randomizer {
+    values: Rc<RefCell<Vec<u64>>>,
+}
+
+impl Process for Randomizer {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, _content: String) {
+        let values = self.values.clone();
+        spawn(async move {
+            sleep(Duration::from_millis(100)).await;
+            let value = random::<u64>();
+            let ranged = random_range(0..1000);
+            values.borrow_mut().extend([value, ranged]);
+        });
+    }
+
+    fn hash(&self) -> HashType {
+        self.values
+            .borrow()
+            .iter()
+            .fold(0, |h, v| h ^ *v as HashType)
+    }
+}
+
+fn add_randomizer(system: &SystemHandle, node: &str) {
+    let mut n = Node::new(node);
+    let randomizer = Randomizer {
+        values: Default::default(),
+    };
+    n.add_proc("randomizer", randomizer).unwrap();
+    system.add_node(n).unwrap();
+    system
+        .send_local(&Address::new(node, "randomizer"), "random")
+        .unwrap();
+}
+
+fn build(system: SystemHandle) {
+    add_randomizer(&system, "n");
+}
+
+fn values_of(system: &SystemHandle, node: &str) -> Vec<u64> {
+    let randomizer = system
+        .proc_state::<Randomizer>(Address::new(node, "randomizer"))
+        .unwrap();
+    let values = randomizer.borrow().values.borrow().clone();
+    values
+}
+
+fn values(system: &SystemHandle) -> Vec<u64> {
+    values_of(system, "n")
+}
+
+fn collect(dfs: bool) -> Vec<Vec<u64>> {
+    let cfg = SearchConfigBuilder::no_faults().build();
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    let goal = |v: StateView| match values(&v.system()).len() {
+        2 => Ok(()),
+        _ => Err("no values".to_string()),
+    };
+    let invariant = |_| Ok(());
+    if dfs {
+        checker.collect(invariant, |_| false, goal, mc::DfsSearcher::new(cfg))
+    } else {
+        checker.collect(invariant, |_| false, goal, mc::BfsSearcher::new(cfg))
+    }
+    .unwrap();
+    let result = Arc::new(Mutex::new(Vec::new()));
+    let r = result.clone();
+    checker.for_each(move |system| r.lock().unwrap().push(values(&system)));
+    let result = result.lock().unwrap().clone();
+    result
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn fixed_values() {
+    let collected = collect(false);
+    assert_eq!(collected.len(), 1);
+    let values = &collected[0];
+    assert_ne!(values[0], values[1]);
+    assert!(values[1] < 1000);
+
+    // values do not depend on the search
+    assert_eq!(collect(false), collected);
+    assert_eq!(collect(true), collected);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn values_do_not_depend_on_order() {
+    let build = |system: SystemHandle| {
+        add_randomizer(&system, "n1");
+        add_randomizer(&system, "n2");
+    };
+    let cfg = SearchConfigBuilder::no_faults().build();
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    let goal = |v: StateView| {
+        let system = v.system();
+        match (
+            values_of(&system, "n1").len(),
+            values_of(&system, "n2").len(),
+        ) {
+            (2, 2) => Ok(()),
+            _ => Err("no values".to_string()),
+        }
+    };
+    checker
+        .collect(|_| Ok(()), |_| false, goal, mc::BfsSearcher::new(cfg))
+        .unwrap();
+
+    // both orders of the timers give the same values
+    assert_eq!(checker.states_count(), 1);
+    let result = Arc::new(Mutex::new(Vec::new()));
+    let r = result.clone();
+    checker.for_each(move |system| {
+        let values = [values_of(&system, "n1"), values_of(&system, "n2")];
+        r.lock().unwrap().push(values)
+    });
+    let [n1, n2] = result.lock().unwrap()[0].clone();
+    assert_ne!(n1[0], n2[0]);
+}
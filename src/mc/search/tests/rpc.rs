@@ -77,7 +77,11 @@ fn basic() {
     let gen = Rc::new(RefCell::new(Generator::new()));
     let net = NetConfig::new(Duration::from_millis(100), Duration::from_millis(200)).unwrap();
     let system = System::new(&net, &(gen.clone() as Rc<RefCell<dyn EventDriver>>));
-    let mut state = SearchState { system, gen };
+    let mut state = SearchState {
+        system,
+        gen,
+        depth: 0,
+    };
 
     let system = state.system.handle();
 
@@ -122,7 +126,11 @@ fn two_sends() {
     let gen = Rc::new(RefCell::new(Generator::new()));
     let net = NetConfig::new(Duration::from_millis(100), Duration::from_millis(200)).unwrap();
     let system = System::new(&net, &(gen.clone() as Rc<RefCell<dyn EventDriver>>));
-    let mut state = SearchState { system, gen };
+    let mut state = SearchState {
+        system,
+        gen,
+        depth: 0,
+    };
 
     let system = state.system.handle();
 
@@ -0,0 +1,132 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    mc::{self, error::SearchErrorKind, ScheduleConstraint, SearchConfigBuilder, StateView},
+    model::{net::send_message, node::Node, SystemHandle},
+    Address, HashType, Process,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Sends its name to the others and records the received names.
+struct Voter {
+    others: Vec<Address>,
+    me: String,
+    received: Vec<String>,
+}
+
+impl Process for Voter {
+    fn on_message(&mut self, _from: Address, content: String) {
+        self.received.push(content);
+    }
+
+    fn on_local_message(&mut self, _content: String) {
+        for other in self.others.iter() {
+            send_message(other, &self.me);
+        }
+    }
+
+    fn hash(&self) -> HashType {
+        self.received
+            .iter()
+            .fold(0, |h, s| h * 31 + s.as_bytes()[0] as HashType)
+    }
+}
+
+fn build(system: SystemHandle) {
+    for i in 0..3 {
+        let mut node = Node::new(i.to_string());
+        let voter = Voter {
+            others: (0..3)
+                .filter(|j| *j != i)
+                .map(|j| Address::new(j.to_string(), "voter"))
+                .collect(),
+            me: i.to_string(),
+            received: Vec::new(),
+        };
+        node.add_proc("voter", voter).unwrap();
+        system.add_node(node).unwrap();
+    }
+    for i in 0..3 {
+        system
+            .send_local(&Address::new(i.to_string(), "voter"), "vote")
+            .unwrap();
+    }
+}
+
+fn received(system: &SystemHandle, node: &str) -> Vec<String> {
+    let voter = system
+        .proc_state::<Voter>(Address::new(node, "voter"))
+        .unwrap();
+    let received = voter.borrow().received.clone();
+    received
+}
+
+fn ordered(v: StateView) -> Result<(), String> {
+    match received(&v.system(), "0").as_slice() {
+        [first, ..] if first == "2" => Err("vote of 2 received first".into()),
+        _ => Ok(()),
+    }
+}
+
+fn finished(v: StateView) -> Result<(), String> {
+    match (0..3).all(|i| received(&v.system(), &i.to_string()).len() == 2) {
+        true => Ok(()),
+        false => Err("not finished".into()),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn follow_prefix() {
+    let cfg = SearchConfigBuilder::no_faults().max_msg_drops(0).build();
+
+    // without the schedule the vote of 2 can be received first
+    let err = mc::ModelChecker::new_with_build(build)
+        .check(
+            ordered,
+            |_| false,
+            finished,
+            mc::BfsSearcher::new(cfg.clone()),
+        )
+        .unwrap_err();
+    assert!(matches!(err.kind, SearchErrorKind::InvariantViolation(_)));
+
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    let schedule = [
+        ScheduleConstraint::udp("1:voter", "0:voter"),
+        ScheduleConstraint::new().kind("SelectUdp").from("2:voter"),
+    ];
+    let log = checker.follow(&schedule, &cfg, |_| Ok(())).unwrap();
+    assert_eq!(log.steps["SelectUdp"], 3);
+    assert_eq!(checker.states_count(), 2);
+
+    let result = Arc::new(Mutex::new(Vec::new()));
+    let r = result.clone();
+    checker.for_each(move |system| {
+        r.lock().unwrap().push(received(&system, "0"));
+    });
+    assert!(result.lock().unwrap().iter().all(|r| r[0] == "1"));
+
+    checker
+        .check(ordered, |_| false, finished, mc::DfsSearcher::new(cfg))
+        .unwrap();
+}
+
+#[test]
+fn unmatched_constraint() {
+    let cfg = SearchConfigBuilder::no_faults().max_msg_drops(0).build();
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    let schedule = [
+        ScheduleConstraint::udp("1:voter", "0:voter"),
+        ScheduleConstraint::udp("1:voter", "0:voter"),
+    ];
+    let err = checker.follow(&schedule, &cfg, |_| Ok(())).unwrap_err();
+    let SearchErrorKind::ScheduleUnmatched(unmatched) = &err.kind else {
+        panic!("unexpected error: {}", err);
+    };
+    assert_eq!(unmatched.index, 1);
+    assert_eq!(unmatched.constraint, "SelectUdp from 1:voter to 0:voter");
+    assert_eq!(unmatched.trace.depth(), 2);
+}
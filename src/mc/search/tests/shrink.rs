@@ -0,0 +1,147 @@
+use crate::{
+    mc::{
+        self,
+        search::{state::StateTrace, step::StateTraceStep},
+        SearchConfig, SearchConfigBuilder, StateView,
+    },
+    model::{net::send_message, node::Node, SystemHandle},
+    send_local, Address, HashType, Process,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+struct Sender {}
+
+impl Process for Sender {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, content: String) {
+        let count: usize = content.parse().unwrap();
+        for i in 0..count {
+            send_message(&Address::new("n2", "sink"), i.to_string());
+        }
+    }
+
+    fn hash(&self) -> HashType {
+        0
+    }
+}
+
+struct Sink {
+    received: Vec<String>,
+}
+
+impl Process for Sink {
+    fn on_message(&mut self, _from: Address, content: String) {
+        self.received.push(content.clone());
+        send_local(content);
+    }
+
+    fn on_local_message(&mut self, _content: String) {
+        unreachable!()
+    }
+
+    fn hash(&self) -> HashType {
+        self.received.len() as HashType
+    }
+}
+
+fn build(system: SystemHandle) {
+    let mut node = Node::new("n1");
+    node.add_proc("sender", Sender {}).unwrap();
+    system.add_node(node).unwrap();
+
+    let mut node = Node::new("n2");
+    node.add_proc("sink", Sink { received: vec![] }).unwrap();
+    system.add_node(node).unwrap();
+
+    // node without events, which is crashed in the tests
+    let mut node = Node::new("n3");
+    node.add_proc("sink", Sink { received: vec![] }).unwrap();
+    system.add_node(node).unwrap();
+
+    system
+        .send_local(&Address::new("n1", "sender"), "5")
+        .unwrap();
+}
+
+fn invariant(view: StateView) -> Result<(), String> {
+    let locals = view.system().read_locals("n2", "sink").unwrap();
+    if locals.iter().any(|m| m == "3") {
+        Err("message 3 received".into())
+    } else {
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn drop_irrelevant_steps() {
+    let checker = mc::ModelChecker::new_with_build(build);
+    let searcher = mc::DfsSearcher::new(SearchConfig::no_faults_no_drops());
+    let err = checker
+        .check(invariant, |_| false, |_| Err("no goal".into()), searcher)
+        .unwrap_err();
+    let trace = err.kind.trace().unwrap();
+    assert!(trace.depth() > 2);
+
+    // build and delivery of the message 3
+    let cfg = SearchConfig::no_faults_no_drops();
+    let shrunk = mc::shrink(trace, &cfg, invariant).unwrap();
+    assert_eq!(shrunk.trace.depth(), 2);
+    assert_eq!(shrunk.report, "message 3 received");
+    println!("{}", shrunk);
+
+    // shrunk trace is valid
+    let state = mc::search::state::SearchState::from_trace(&shrunk.trace).unwrap();
+    let view = StateView::new(&state, shrunk.trace.clone());
+    assert!(invariant(view).is_err());
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn not_violated() {
+    let checker = mc::ModelChecker::new_with_build(build);
+    let searcher = mc::BfsSearcher::new(SearchConfig::no_faults_no_drops());
+    let err = checker
+        .check(invariant, |_| false, |_| Err("no goal".into()), searcher)
+        .unwrap_err();
+    let trace = err.kind.trace().unwrap();
+    let cfg = SearchConfig::no_faults_no_drops();
+    assert!(mc::shrink(trace, &cfg, |_| Ok(())).is_none());
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn move_fault_after_violation() {
+    let checker = mc::ModelChecker::new_with_build(build);
+    let searcher = mc::DfsSearcher::new(SearchConfig::no_faults_no_drops());
+    let err = checker
+        .check(invariant, |_| false, |_| Err("no goal".into()), searcher)
+        .unwrap_err();
+
+    // crash of the idle node can not be dropped, but can be moved after the violation
+    let mut trace = StateTrace::new();
+    for (i, step) in err.kind.trace().unwrap().steps().iter().enumerate() {
+        trace.add_step(step.clone());
+        if i == 0 {
+            trace.add_step(StateTraceStep::CrashNode(2));
+        }
+    }
+    let cfg = SearchConfigBuilder::no_faults()
+        .max_msg_drops(0)
+        .max_node_faults(1)
+        .build();
+    let shrunk = mc::shrink(&trace, &cfg, invariant).unwrap();
+    assert_eq!(shrunk.trace.depth(), 2);
+    assert_eq!(shrunk.report, "message 3 received");
+
+    // crash is not available in the search without faults
+    let cfg = SearchConfig::no_faults_no_drops();
+    assert!(mc::shrink(&trace, &cfg, invariant).is_none());
+}
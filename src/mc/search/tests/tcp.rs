@@ -107,7 +107,11 @@ fn collect() {
     let net = NetConfig::new(Duration::from_millis(100), Duration::from_millis(200)).unwrap();
     let system = System::new(&net, &(gen.clone() as Rc<RefCell<dyn EventDriver>>));
     build_system(system.handle());
-    let mut state = SearchState { system, gen };
+    let mut state = SearchState {
+        system,
+        gen,
+        depth: 0,
+    };
 
     // apply first step (timer)
     let cfg = SearchConfig::no_faults_no_drops();
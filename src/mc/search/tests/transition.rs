@@ -0,0 +1,138 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{
+    mc::{
+        self,
+        error::SearchErrorKind,
+        search::tests::common::{self, add_coin, add_tickers, flips},
+        SearchConfigBuilder, StateView,
+    },
+    model::SystemHandle,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+fn build(system: SystemHandle) {
+    add_tickers(&system, &["node"], Some(5));
+}
+
+fn ticks(v: &StateView) -> u64 {
+    common::ticks(v, "node")
+}
+
+fn finished(v: StateView) -> Result<(), String> {
+    if ticks(&v) == 5 {
+        Ok(())
+    } else {
+        Err("not finished".into())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn monotonic() {
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    checker.transition_invariant(|prev, next, _| {
+        if ticks(&next) == ticks(&prev) + 1 {
+            Ok(())
+        } else {
+            Err(format!("{} -> {}", ticks(&prev), ticks(&next)))
+        }
+    });
+    let cfg = SearchConfigBuilder::no_faults().build();
+    checker
+        .check(|_| Ok(()), |_| false, finished, mc::DfsSearcher::new(cfg))
+        .unwrap();
+}
+
+#[test]
+fn violation() {
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    checker.transition_invariant(|prev, next, step| {
+        if ticks(&prev) == 2 && ticks(&next) == 3 {
+            Err(format!("tick 3 on {}", step))
+        } else {
+            Ok(())
+        }
+    });
+    let cfg = SearchConfigBuilder::no_faults().build();
+    let err = checker
+        .check(|_| Ok(()), |_| false, finished, mc::BfsSearcher::new(cfg))
+        .unwrap_err();
+    let SearchErrorKind::InvariantViolation(violation) = err.kind else {
+        panic!("unexpected error: {}", err);
+    };
+    assert_eq!(violation.trace.depth(), 4);
+    assert!(violation.report.starts_with("tick 3 on "));
+}
+
+#[test]
+fn collect() {
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    checker.transition_invariant(|prev, next, _| {
+        if ticks(&next) == 4 && ticks(&prev) == 3 {
+            Err("tick 4".into())
+        } else {
+            Ok(())
+        }
+    });
+    let cfg = SearchConfigBuilder::no_faults().build();
+    let goal = |v: StateView| {
+        if ticks(&v) == 3 {
+            Ok(())
+        } else {
+            Err("not collected".into())
+        }
+    };
+    checker
+        .collect(
+            |_| Ok(()),
+            |_| false,
+            goal,
+            mc::BfsSearcher::new(cfg.clone()),
+        )
+        .unwrap();
+    assert_eq!(checker.states_count(), 1);
+
+    // the next search starts from the collected states
+    let err = checker
+        .check(|_| Ok(()), |_| false, finished, mc::DfsSearcher::new(cfg))
+        .unwrap_err();
+    assert!(matches!(err.kind, SearchErrorKind::InvariantViolation(..)));
+}
+
+#[test]
+fn previous_state_kept() {
+    static BUILDS: AtomicUsize = AtomicUsize::new(0);
+    fn build(system: SystemHandle) {
+        BUILDS.fetch_add(1, Ordering::SeqCst);
+        add_coin(&system, 3);
+    }
+    let goal = |v: StateView| match flips(&v.system()).len() {
+        3 => Ok(()),
+        _ => Err("not finished".into()),
+    };
+    for dfs in [false, true] {
+        let mut checker = mc::ModelChecker::new_with_build(build);
+        checker.transition_invariant(|prev, next, _| {
+            let (prev, next) = (flips(&prev.system()), flips(&next.system()));
+            if next.len() == prev.len() + 1 && next.starts_with(&prev) {
+                Ok(())
+            } else {
+                Err(format!("{:?} -> {:?}", prev, next))
+            }
+        });
+        let cfg = SearchConfigBuilder::no_faults().max_snapshots(100).build();
+        BUILDS.store(0, Ordering::SeqCst);
+        let log = if dfs {
+            checker.collect(|_| Ok(()), |_| false, goal, mc::DfsSearcher::new(cfg))
+        } else {
+            checker.collect(|_| Ok(()), |_| false, goal, mc::BfsSearcher::new(cfg))
+        }
+        .unwrap();
+        assert_eq!(checker.states_count(), 8);
+        // every state is made once, the previous states are not replayed
+        assert_eq!(BUILDS.load(Ordering::SeqCst), log.visited_total);
+    }
+}
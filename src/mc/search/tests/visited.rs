@@ -0,0 +1,67 @@
+use crate::{
+    mc::{
+        self,
+        search::tests::common::{add_coin, flips},
+        BitstateSet, DiskVisitedSet, SearchConfigBuilder, StateView, VisitedSet,
+    },
+    model::SystemHandle,
+    util::hash::fingerprint,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+fn build(system: SystemHandle) {
+    add_coin(&system, 3);
+}
+
+fn finished(v: StateView) -> Result<(), String> {
+    match flips(&v.system()).len() {
+        3 => Ok(()),
+        _ => Err("not finished".into()),
+    }
+}
+
+fn check(checker: mc::ModelChecker) -> mc::SearchLog {
+    let cfg = SearchConfigBuilder::no_faults().build();
+    checker
+        .check(|_| Ok(()), |_| false, finished, mc::DfsSearcher::new(cfg))
+        .unwrap()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn backends_same_as_memory() {
+    let log = check(mc::ModelChecker::new_with_build(build));
+    assert_eq!(log.visited_unique, 15);
+
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    checker.visited_set(|| BitstateSet::new(1 << 16));
+    assert_eq!(check(checker).visited_unique, 15);
+
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    checker.visited_set(|| DiskVisitedSet::new(std::env::temp_dir(), 4));
+    assert_eq!(check(checker).visited_unique, 15);
+}
+
+#[test]
+fn bitstate_omits_states() {
+    let mut set = BitstateSet::new(8).hashes(2);
+    let new = (0..1000).filter(|i| set.insert(fingerprint(i))).count();
+    assert!(new <= 64);
+    assert!(set.contains(fingerprint(&0)));
+    assert!(set.omission_probability() > 0.9);
+}
+
+#[test]
+fn disk_spills_sorted() {
+    let mut set = DiskVisitedSet::new(std::env::temp_dir(), 100);
+    assert!((0..2000).all(|i| set.insert(fingerprint(&i))));
+    assert!(set.spilled() >= 1900);
+    assert_eq!(set.len(), 2000);
+
+    assert!((0..2000).all(|i| !set.insert(fingerprint(&i))));
+    assert!((0..2000).all(|i| set.contains(fingerprint(&i))));
+    assert!((2000..3000).all(|i| !set.contains(fingerprint(&i))));
+    assert_eq!(set.len(), 2000);
+}
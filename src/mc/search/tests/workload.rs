@@ -0,0 +1,164 @@
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    mc::{self, error::SearchErrorKind, SearchConfig, SearchConfigBuilder, StateView, Workload},
+    model::{node::Node, SystemHandle},
+    Address, HashType, Process,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Records received local messages.
+#[derive(Default)]
+struct Recorder {
+    received: Vec<String>,
+}
+
+impl Process for Recorder {
+    fn on_message(&mut self, _from: Address, _content: String) {
+        unreachable!()
+    }
+
+    fn on_local_message(&mut self, content: String) {
+        self.received.push(content);
+    }
+
+    fn hash(&self) -> HashType {
+        self.received
+            .iter()
+            .fold(0, |h, s| h * 31 + s.as_bytes()[0] as HashType)
+    }
+}
+
+fn build(system: SystemHandle) {
+    let mut node = Node::new("n");
+    node.add_proc("recorder", Recorder::default()).unwrap();
+    system.add_node(node).unwrap();
+}
+
+fn received(system: &SystemHandle) -> String {
+    let recorder = system
+        .proc_state::<Recorder>(Address::new("n", "recorder"))
+        .unwrap();
+    let received = recorder.borrow().received.concat();
+    received
+}
+
+fn workload() -> Workload {
+    let mut workload = Workload::new();
+    let a = workload.send_local("n:recorder", "a");
+    let b = workload.send_local("n:recorder", "b");
+    workload.send_local("n:recorder", "c");
+    workload.after(b, a);
+    workload
+}
+
+fn collect(cfg: SearchConfig, len: usize) -> BTreeSet<String> {
+    let mut checker = mc::ModelChecker::new_with_build(build);
+    let goal = move |v: StateView| match received(&v.system()).len() == len {
+        true => Ok(()),
+        false => Err("not received".to_string()),
+    };
+    checker
+        .collect(|_| Ok(()), |_| false, goal, mc::BfsSearcher::new(cfg))
+        .unwrap();
+    let result = Arc::new(Mutex::new(BTreeSet::new()));
+    let r = result.clone();
+    checker.for_each(move |system| {
+        r.lock().unwrap().insert(received(&system));
+    });
+    let result = result.lock().unwrap().clone();
+    result
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn ordered_interleavings() {
+    let cfg = SearchConfigBuilder::no_faults()
+        .workload(workload())
+        .build();
+    let collected = collect(cfg, 3);
+    let expected: BTreeSet<String> = ["abc", "acb", "cab"].map(String::from).into();
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn local_messages_budget() {
+    let cfg = SearchConfigBuilder::no_faults()
+        .workload(workload())
+        .max_local_messages(2)
+        .build();
+    let collected = collect(cfg.clone(), 2);
+    let expected: BTreeSet<String> = ["ab", "ac", "ca"].map(String::from).into();
+    assert_eq!(collected, expected);
+    assert!(collect(cfg, 3).is_empty());
+}
+
+#[test]
+fn violation_replayed() {
+    let invariant = |v: StateView| match received(&v.system()).as_str() {
+        "ca" => Err("c before a".to_string()),
+        _ => Ok(()),
+    };
+    let path =
+        std::env::temp_dir().join(format!("dsbuild-mc-workload-{}.json", std::process::id()));
+    let cfg = SearchConfigBuilder::no_faults()
+        .workload(workload())
+        .build();
+    let err = mc::ModelChecker::new_with_build(build)
+        .check(
+            invariant,
+            |_| false,
+            |v: StateView| match received(&v.system()).len() {
+                3 => Ok(()),
+                _ => Err("not received".into()),
+            },
+            mc::DfsSearcher::new(cfg),
+        )
+        .unwrap_err();
+    let SearchErrorKind::InvariantViolation(violation) = &err.kind else {
+        panic!("unexpected error: {}", err);
+    };
+    assert!(violation
+        .trace
+        .to_string()
+        .contains("Send local message 2 to n:recorder"));
+
+    err.save_trace(&path).unwrap();
+    let checker = mc::ModelChecker::new_with_build(build);
+    let replayed = checker.replay(&path, invariant).unwrap();
+    assert!(matches!(
+        replayed,
+        Some(SearchErrorKind::InvariantViolation(_))
+    ));
+    std::fs::remove_file(path).unwrap();
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[should_panic(expected = "unknown message 3")]
+#[test]
+fn after_unknown_message() {
+    let mut workload = workload();
+    workload.after(3, 0);
+}
+
+#[should_panic(expected = "makes a cycle")]
+#[test]
+fn after_self() {
+    let mut workload = workload();
+    workload.after(2, 2);
+}
+
+#[should_panic(expected = "makes a cycle")]
+#[test]
+fn after_cycle() {
+    let mut workload = workload();
+    let d = workload.send_local("n:recorder", "d");
+    workload.after(d, 1);
+    workload.after(0, d);
+}
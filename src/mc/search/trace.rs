@@ -0,0 +1,158 @@
+//! Stable JSON representation of the state traces,
+//! which allows to save counterexamples and replay them later.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    control::ApplyFunctor,
+    error::ReplayError,
+    state::StateTrace,
+    step::{
+        FsEvent, LocalMessage, RpcEvent, RpcMessage, StateTraceStep, TcpEvent, TcpPacket, Timer,
+        UdpMessage,
+    },
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Version of the trace format.
+/// Must be increased on every incompatible change of the format.
+const TRACE_VERSION: u32 = 1;
+
+/// Apply functions, which can be referenced by the trace, by their names.
+pub(crate) type ApplyRegistry = BTreeMap<String, Box<dyn ApplyFunctor>>;
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Serialize, Deserialize)]
+struct TraceRecord {
+    version: u32,
+    steps: Vec<StepRecord>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StepRecord {
+    SelectUdp(UdpMessage),
+    SelectTimer(Timer),
+    SelectTcpPacket(TcpPacket),
+    SelectTcpEvent(TcpEvent),
+    SelectRpcMessage(RpcMessage),
+    SelectRpcEvent(RpcEvent),
+    SelectFsEvent(FsEvent),
+    FailFsEvent(FsEvent),
+    CrashNode {
+        node: usize,
+    },
+    ShutdownNode {
+        node: usize,
+    },
+    RestartNode {
+        node: usize,
+    },
+    CrashFs {
+        node: usize,
+    },
+    Partition {
+        nodes: Vec<usize>,
+    },
+    Heal,
+    Apply {
+        name: String,
+    },
+    SendLocal(LocalMessage),
+    Choose {
+        step: Box<StepRecord>,
+        choices: Vec<usize>,
+    },
+}
+
+impl From<&StateTraceStep> for StepRecord {
+    fn from(step: &StateTraceStep) -> Self {
+        match step.clone() {
+            StateTraceStep::SelectUdp(_, e) => Self::SelectUdp(e),
+            StateTraceStep::SelectTimer(_, e) => Self::SelectTimer(e),
+            StateTraceStep::SelectTcpPacket(_, e) => Self::SelectTcpPacket(e),
+            StateTraceStep::SelectTcpEvent(_, e) => Self::SelectTcpEvent(e),
+            StateTraceStep::SelectRpcMessage(_, e) => Self::SelectRpcMessage(e),
+            StateTraceStep::SelectRpcEvent(_, e) => Self::SelectRpcEvent(e),
+            StateTraceStep::SelectFsEvent(_, e) => Self::SelectFsEvent(e),
+            StateTraceStep::FailFsEvent(_, e) => Self::FailFsEvent(e),
+            StateTraceStep::CrashNode(node) => Self::CrashNode { node },
+            StateTraceStep::ShutdownNode(node) => Self::ShutdownNode { node },
+            StateTraceStep::RestartNode(node) => Self::RestartNode { node },
+            StateTraceStep::CrashFs(node) => Self::CrashFs { node },
+            StateTraceStep::Partition(nodes) => Self::Partition { nodes },
+            StateTraceStep::Heal => Self::Heal,
+            StateTraceStep::Apply(f) => Self::Apply {
+                name: f.name().to_string(),
+            },
+            StateTraceStep::SendLocal(msg) => Self::SendLocal(msg),
+            StateTraceStep::Choose(step, choices) => Self::Choose {
+                step: Box::new(step.as_ref().into()),
+                choices,
+            },
+        }
+    }
+}
+
+impl StepRecord {
+    fn into_step(self, registry: &ApplyRegistry) -> Result<StateTraceStep, ReplayError> {
+        let step = match self {
+            Self::SelectUdp(e) => StateTraceStep::SelectUdp(e.event_id, e),
+            Self::SelectTimer(e) => StateTraceStep::SelectTimer(e.event_id, e),
+            Self::SelectTcpPacket(e) => StateTraceStep::SelectTcpPacket(e.event_id, e),
+            Self::SelectTcpEvent(e) => StateTraceStep::SelectTcpEvent(e.event_id, e),
+            Self::SelectRpcMessage(e) => StateTraceStep::SelectRpcMessage(e.event_id, e),
+            Self::SelectRpcEvent(e) => StateTraceStep::SelectRpcEvent(e.event_id, e),
+            Self::SelectFsEvent(e) => StateTraceStep::SelectFsEvent(e.event_id, e),
+            Self::FailFsEvent(e) => StateTraceStep::FailFsEvent(e.event_id, e),
+            Self::CrashNode { node } => StateTraceStep::CrashNode(node),
+            Self::ShutdownNode { node } => StateTraceStep::ShutdownNode(node),
+            Self::RestartNode { node } => StateTraceStep::RestartNode(node),
+            Self::CrashFs { node } => StateTraceStep::CrashFs(node),
+            Self::Partition { nodes } => StateTraceStep::Partition(nodes),
+            Self::Heal => StateTraceStep::Heal,
+            Self::Apply { name } => {
+                let f = registry.get(&name).ok_or(ReplayError::UnknownApply(name))?;
+                StateTraceStep::Apply(f.clone())
+            }
+            Self::SendLocal(msg) => StateTraceStep::SendLocal(msg),
+            Self::Choose { step, choices } => {
+                StateTraceStep::Choose(Box::new(step.into_step(registry)?), choices)
+            }
+        };
+        Ok(step)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+pub(crate) fn trace_to_json(trace: &StateTrace) -> String {
+    let record = TraceRecord {
+        version: TRACE_VERSION,
+        steps: trace.steps().iter().map(StepRecord::from).collect(),
+    };
+    serde_json::to_string_pretty(&record).expect("trace is always serializable")
+}
+
+pub(crate) fn trace_from_json(
+    json: &str,
+    registry: &ApplyRegistry,
+) -> Result<StateTrace, ReplayError> {
+    let record: TraceRecord =
+        serde_json::from_str(json).map_err(|e| ReplayError::Format(e.to_string()))?;
+    if record.version != TRACE_VERSION {
+        return Err(ReplayError::Format(format!(
+            "unsupported trace version {}, expected {}",
+            record.version, TRACE_VERSION
+        )));
+    }
+    let mut trace = StateTrace::new();
+    for step in record.steps {
+        trace.add_step(step.into_step(registry)?);
+    }
+    Ok(trace)
+}
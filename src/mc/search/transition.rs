@@ -0,0 +1,75 @@
+use std::{rc::Rc, sync::Arc};
+
+use crate::mc::StateView;
+
+use super::{
+    control::TransitionInvariantFn,
+    state::{SearchState, Snapshots, StateTrace},
+    step::StateTraceStep,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+type CheckFn =
+    Arc<dyn Fn(StateView, StateView, &StateTraceStep) -> Result<(), String> + Send + Sync>;
+
+/// Invariants over the transitions between states, which are checked by the searchers
+/// (see [`crate::mc::ModelChecker::transition_invariant`]).
+///
+/// The expanded state is kept as the previous state shared by its children
+/// if the limit of snapshots is not reached (see [`crate::mc::SearchConfig::max_snapshots`]).
+/// Otherwise the previous state is replayed from its trace.
+#[derive(Clone, Default)]
+pub struct Transitions {
+    checks: Vec<CheckFn>,
+}
+
+impl Transitions {
+    pub(crate) fn add(&mut self, f: impl TransitionInvariantFn) {
+        self.checks.push(Arc::new(f));
+    }
+
+    /// Keep the expanded state for its children.
+    /// Returns the previous state shared by the children if there are transition invariants,
+    /// or the snapshot of the child, which can be resumed from the state, otherwise.
+    pub(crate) fn keep(
+        &self,
+        state: SearchState,
+        snapshots: &mut Snapshots,
+    ) -> (Option<Rc<SearchState>>, Option<SearchState>) {
+        if self.checks.is_empty() {
+            (None, snapshots.store(state))
+        } else {
+            (snapshots.share(state), None)
+        }
+    }
+
+    /// Check invariants over the transition, which led to the state of the provided view.
+    /// The previous state is replayed from its trace if it is not provided.
+    /// Transitions made by the applied functions are not checked.
+    pub(crate) fn check(&self, prev: Option<&SearchState>, next: &StateView) -> Result<(), String> {
+        let steps = next.trace().steps();
+        let Some(step) = steps.last() else {
+            return Ok(());
+        };
+        if self.checks.is_empty() || matches!(step.inner(), StateTraceStep::Apply(_)) {
+            return Ok(());
+        }
+        let mut trace = StateTrace::new();
+        steps[..steps.len() - 1]
+            .iter()
+            .for_each(|s| trace.add_step(s.clone()));
+        let replayed;
+        let prev = match prev {
+            Some(state) => state,
+            None => {
+                replayed = SearchState::from_trace(&trace).map_err(|e| e.to_string())?;
+                &replayed
+            }
+        };
+        let prev = StateView::new(prev, trace);
+        self.checks
+            .iter()
+            .try_for_each(|f| f(prev.clone(), next.clone(), step))
+    }
+}
@@ -0,0 +1,72 @@
+//! Invariant violations found by the search in the keep going mode
+//! (see [`SearchConfig::max_violations`]).
+
+use std::collections::HashSet;
+
+use crate::model::system::HashType;
+
+use super::{
+    config::SearchConfig,
+    error::{InvariantViolation, SearchError, SearchErrorKind},
+    log::SearchLog,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+pub(crate) struct Violations {
+    max: Option<usize>,
+    found: Vec<InvariantViolation>,
+    seen: HashSet<(String, HashType)>,
+}
+
+impl Violations {
+    pub fn new(cfg: &SearchConfig) -> Self {
+        Self {
+            max: cfg.max_violations,
+            found: Vec::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Records violation found in the state with provided key of the visited states.
+    /// Returns error if the search must be stopped.
+    pub fn add(
+        &mut self,
+        violation: InvariantViolation,
+        hash: HashType,
+        log: &SearchLog,
+    ) -> Result<(), SearchError> {
+        let Some(max) = self.max else {
+            let kind = SearchErrorKind::InvariantViolation(violation);
+            return Err(SearchError::new(kind, log));
+        };
+        if self.seen.insert((violation.report.clone(), hash)) {
+            self.found.push(violation);
+        }
+        if self.found.len() >= max {
+            let kind = SearchErrorKind::InvariantViolations(std::mem::take(&mut self.found));
+            return Err(SearchError::new(kind, log));
+        }
+        Ok(())
+    }
+
+    /// Returns error, which stops the search, or the found violations instead of it,
+    /// so they are not lost.
+    pub fn stop(&mut self, err: SearchError) -> SearchError {
+        if self.found.is_empty() {
+            return err;
+        }
+        let kind = SearchErrorKind::InvariantViolations(std::mem::take(&mut self.found));
+        SearchError::new(kind, &err.log)
+    }
+
+    /// Returns error if some violations were found.
+    pub fn finish(self, log: &SearchLog) -> Result<(), SearchError> {
+        if self.found.is_empty() {
+            Ok(())
+        } else {
+            let kind = SearchErrorKind::InvariantViolations(self.found);
+            Err(SearchError::new(kind, log))
+        }
+    }
+}
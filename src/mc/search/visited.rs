@@ -0,0 +1,301 @@
+//! Sets of the visited states, by which the searchers deduplicate the states
+//! (see [`crate::mc::ModelChecker::visited_set`]).
+
+use std::{
+    collections::{BTreeSet, HashSet},
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::model::system::HashType;
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Set of the hashes of the visited states.
+///
+/// The set is shared by the worker threads of [`crate::mc::ParallelSearcher`],
+/// so it must be sendable.
+pub trait VisitedSet: Send {
+    /// Marks state with the provided hash as visited.
+    /// Returns if the state was not visited before.
+    fn insert(&mut self, hash: HashType) -> bool;
+
+    /// Returns if the state with the provided hash was visited.
+    fn contains(&self, hash: HashType) -> bool;
+}
+
+/// Exact set kept in memory, used by default.
+impl VisitedSet for HashSet<HashType> {
+    fn insert(&mut self, hash: HashType) -> bool {
+        HashSet::insert(self, hash)
+    }
+
+    fn contains(&self, hash: HashType) -> bool {
+        HashSet::contains(self, &hash)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Bitstate hashing set, which is the bloom filter of the fixed memory size.
+///
+/// Every state is marked by several bits of the filter,
+/// so the set is not exact: the new state can be considered as visited
+/// if all its bits are marked by the other states, and then it is not explored.
+/// The search is not exhaustive in this case,
+/// but covers the most of the state space which does not fit into the memory.
+pub struct BitstateSet {
+    bits: Vec<u64>,
+    hashes: usize,
+    marked: usize,
+}
+
+impl BitstateSet {
+    /// Make new set, which uses the provided number of bytes.
+    /// Every state is marked by 3 bits.
+    pub fn new(memory: usize) -> Self {
+        Self {
+            bits: vec![0; memory.div_ceil(8).max(1)],
+            hashes: 3,
+            marked: 0,
+        }
+    }
+
+    /// Set number of the bits, which mark every state.
+    pub fn hashes(mut self, hashes: usize) -> Self {
+        assert!(hashes > 0, "state must be marked by at least one bit");
+        self.hashes = hashes;
+        self
+    }
+
+    /// Returns probability that the new state is considered as visited,
+    /// so it is omitted by the search.
+    pub fn omission_probability(&self) -> f64 {
+        let filled = self.marked as f64 / self.bits_count() as f64;
+        filled.powi(self.hashes as i32)
+    }
+
+    fn bits_count(&self) -> u64 {
+        self.bits.len() as u64 * 64
+    }
+
+    /// Returns bits of the state made by the double hashing
+    /// of the halves of the fingerprint.
+    fn positions(&self, hash: HashType) -> impl Iterator<Item = (usize, u64)> {
+        let low = hash as u64;
+        let high = (hash >> 64) as u64 | 1;
+        let count = self.bits_count();
+        (0..self.hashes as u64).map(move |i| {
+            let bit = low.wrapping_add(i.wrapping_mul(high)) % count;
+            ((bit / 64) as usize, 1 << (bit % 64))
+        })
+    }
+}
+
+impl VisitedSet for BitstateSet {
+    fn insert(&mut self, hash: HashType) -> bool {
+        let mut new = false;
+        for (word, mask) in self.positions(hash).collect::<Vec<_>>() {
+            if self.bits[word] & mask == 0 {
+                self.bits[word] |= mask;
+                self.marked += 1;
+                new = true;
+            }
+        }
+        new
+    }
+
+    fn contains(&self, hash: HashType) -> bool {
+        self.positions(hash)
+            .all(|(word, mask)| self.bits[word] & mask != 0)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+const RECORD_SIZE: usize = size_of::<HashType>();
+
+/// Number of the records in the block of the file, which is read on lookup.
+const BLOCK_RECORDS: usize = 256;
+
+static NEXT_FILE: AtomicUsize = AtomicUsize::new(0);
+
+/// Exact set, which keeps limited number of the hashes in memory
+/// and spills them into the sorted file in the provided directory.
+///
+/// The file is merged with the hashes in memory on every spill
+/// and only the first hash of every file block is kept in memory,
+/// so the lookup of the spilled hash reads one block of the file.
+/// The file is removed when the set is dropped.
+///
+/// # Panics
+///
+/// Methods panic if the file can not be written or read.
+pub struct DiskVisitedSet {
+    memory: BTreeSet<HashType>,
+    max_in_memory: usize,
+    path: PathBuf,
+    file: Option<File>,
+    spilled: usize,
+    index: Vec<HashType>,
+}
+
+impl DiskVisitedSet {
+    /// Make new set, which keeps at most `max_in_memory` hashes in memory
+    /// and spills the others into the file in the provided directory.
+    pub fn new(dir: impl AsRef<Path>, max_in_memory: usize) -> Self {
+        let name = format!(
+            "dsbuild-visited-{}-{}.bin",
+            std::process::id(),
+            NEXT_FILE.fetch_add(1, Ordering::Relaxed)
+        );
+        Self {
+            memory: BTreeSet::new(),
+            max_in_memory: max_in_memory.max(1),
+            path: dir.as_ref().join(name),
+            file: None,
+            spilled: 0,
+            index: Vec::new(),
+        }
+    }
+
+    /// Returns number of the visited states.
+    pub fn len(&self) -> usize {
+        self.memory.len() + self.spilled
+    }
+
+    /// Returns if there are no visited states.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns number of the hashes spilled into the file.
+    pub fn spilled(&self) -> usize {
+        self.spilled
+    }
+
+    fn contains_spilled(&self, hash: HashType) -> bool {
+        let Some(mut file) = self.file.as_ref() else {
+            return false;
+        };
+        let block = match self.index.partition_point(|first| *first <= hash) {
+            0 => return false,
+            n => n - 1,
+        };
+        let first = block * BLOCK_RECORDS;
+        let records = BLOCK_RECORDS.min(self.spilled - first);
+        let mut buf = vec![0; records * RECORD_SIZE];
+        file.seek(SeekFrom::Start((first * RECORD_SIZE) as u64))
+            .and_then(|_| file.read_exact(&mut buf))
+            .expect("can not read spilled visited states");
+        let block = buf
+            .chunks_exact(RECORD_SIZE)
+            .map(|r| HashType::from_le_bytes(r.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        block.binary_search(&hash).is_ok()
+    }
+
+    /// Merges the hashes in memory with the spilled ones into the new file.
+    fn spill(&mut self) {
+        self.spill_file()
+            .expect("can not spill visited states into the file");
+    }
+
+    fn spill_file(&mut self) -> std::io::Result<()> {
+        let tmp = self.path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        let mut disk = match self.file.take() {
+            Some(mut file) => {
+                file.seek(SeekFrom::Start(0))?;
+                SpilledReader::new(BufReader::new(file), self.spilled)?
+            }
+            None => SpilledReader::default(),
+        };
+
+        let total = self.spilled + self.memory.len();
+        let mut index = Vec::with_capacity(total.div_ceil(BLOCK_RECORDS));
+        let mut memory = std::mem::take(&mut self.memory).into_iter().peekable();
+        for i in 0..total {
+            let next = match (memory.peek(), disk.peek()) {
+                (Some(m), Some(d)) if *m < d => memory.next().unwrap(),
+                (Some(_), None) => memory.next().unwrap(),
+                _ => disk.next()?,
+            };
+            if i % BLOCK_RECORDS == 0 {
+                index.push(next);
+            }
+            writer.write_all(&next.to_le_bytes())?;
+        }
+        writer.flush()?;
+        drop(writer);
+        drop(disk);
+
+        fs::rename(&tmp, &self.path)?;
+        self.file = Some(File::open(&self.path)?);
+        self.spilled = total;
+        self.index = index;
+        Ok(())
+    }
+}
+
+/// Reads the spilled hashes one by one.
+#[derive(Default)]
+struct SpilledReader {
+    reader: Option<BufReader<File>>,
+    remaining: usize,
+    next: Option<HashType>,
+}
+
+impl SpilledReader {
+    fn new(reader: BufReader<File>, records: usize) -> std::io::Result<Self> {
+        let mut result = Self {
+            reader: Some(reader),
+            remaining: records,
+            next: None,
+        };
+        result.next()?;
+        Ok(result)
+    }
+
+    fn peek(&self) -> Option<HashType> {
+        self.next
+    }
+
+    /// Returns the current hash and reads the next one.
+    fn next(&mut self) -> std::io::Result<HashType> {
+        let current = self.next.take();
+        if self.remaining > 0 {
+            let mut buf = [0; RECORD_SIZE];
+            self.reader.as_mut().unwrap().read_exact(&mut buf)?;
+            self.next = Some(HashType::from_le_bytes(buf));
+            self.remaining -= 1;
+        }
+        Ok(current.unwrap_or_default())
+    }
+}
+
+impl VisitedSet for DiskVisitedSet {
+    fn insert(&mut self, hash: HashType) -> bool {
+        if self.contains_spilled(hash) || !self.memory.insert(hash) {
+            return false;
+        }
+        if self.memory.len() >= self.max_in_memory {
+            self.spill();
+        }
+        true
+    }
+
+    fn contains(&self, hash: HashType) -> bool {
+        self.memory.contains(&hash) || self.contains_spilled(hash)
+    }
+}
+
+impl Drop for DiskVisitedSet {
+    fn drop(&mut self) {
+        if self.file.take().is_some() {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Client workload, which is injected into the system by the searchers
+//! as local messages sent at any point of the search
+//! (see [`crate::mc::SearchConfigBuilder::workload`]).
+
+use std::collections::BTreeSet;
+
+use crate::Address;
+
+use super::step::LocalMessage;
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone)]
+struct WorkloadMessage {
+    to: Address,
+    content: String,
+    after: Vec<usize>,
+}
+
+/// Set of the local messages, which are sent to the processes
+/// by the model checker in any order allowed by the ordering constraints.
+///
+/// Every message is sent at most once along the search path.
+#[derive(Clone, Default)]
+pub struct Workload {
+    messages: Vec<WorkloadMessage>,
+}
+
+impl Workload {
+    /// Create empty workload.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add local message with the provided content sent to the process.
+    /// Returns id of the message, which can be used in the ordering constraints.
+    pub fn send_local(&mut self, to: impl Into<Address>, content: impl Into<String>) -> usize {
+        self.messages.push(WorkloadMessage {
+            to: to.into(),
+            content: content.into(),
+            after: Vec::new(),
+        });
+        self.messages.len() - 1
+    }
+
+    /// Require the message to be sent only after the other message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the messages is unknown or the constraint makes a cycle,
+    /// so the messages of the cycle could never be sent.
+    pub fn after(&mut self, msg: usize, before: usize) -> &mut Self {
+        assert!(msg < self.messages.len(), "unknown message {}", msg);
+        assert!(before < self.messages.len(), "unknown message {}", before);
+        assert!(
+            !self.depends_on(before, msg),
+            "message {} can not be sent after {}, because it makes a cycle",
+            msg,
+            before
+        );
+        self.messages[msg].after.push(before);
+        self
+    }
+
+    /// Returns true if the message is the other one or can be sent only after it.
+    fn depends_on(&self, msg: usize, other: usize) -> bool {
+        let mut visited = BTreeSet::new();
+        let mut stack = vec![msg];
+        while let Some(msg) = stack.pop() {
+            if msg == other {
+                return true;
+            }
+            if visited.insert(msg) {
+                stack.extend(self.messages[msg].after.iter().copied());
+            }
+        }
+        false
+    }
+
+    /// Returns number of the messages in the workload.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns true if the workload has no messages.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Returns messages, which are not sent yet
+    /// and all predecessors of which are sent.
+    pub(crate) fn ready<'a>(
+        &'a self,
+        sent: &'a BTreeSet<usize>,
+    ) -> impl Iterator<Item = LocalMessage> + 'a {
+        self.messages
+            .iter()
+            .enumerate()
+            .filter(|(id, msg)| !sent.contains(id) && msg.after.iter().all(|b| sent.contains(b)))
+            .map(|(id, msg)| LocalMessage {
+                id,
+                node: msg.to.node.clone(),
+                process: msg.to.process.clone(),
+                content: msg.content.clone(),
+            })
+    }
+}
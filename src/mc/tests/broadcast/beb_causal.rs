@@ -1,9 +1,4 @@
-use std::{
-    cell::RefCell,
-    collections::HashMap,
-    hash::{DefaultHasher, Hash, Hasher},
-    rc::Rc,
-};
+use std::{cell::RefCell, collections::HashMap, hash::Hash, rc::Rc};
 
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
@@ -22,6 +17,7 @@ use super::{
 };
 
 use crate::prelude::*;
+use crate::util::hash::Hasher128;
 
 ////////////////////////////////////////////////////////////////////////////////
 /// Best Effort Broadcast
@@ -152,9 +148,9 @@ impl Process for BebProcess {
     }
 
     fn hash(&self) -> HashType {
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = Hasher128::new();
         self.reg.borrow().hash(&mut hasher);
-        hasher.finish()
+        hasher.finish128()
     }
 }
 
@@ -1,9 +1,4 @@
-use std::{
-    cell::RefCell,
-    collections::HashMap,
-    hash::{DefaultHasher, Hash, Hasher},
-    rc::Rc,
-};
+use std::{cell::RefCell, collections::HashMap, hash::Hash, rc::Rc};
 
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
@@ -11,6 +6,7 @@ use super::two_msg::{self};
 
 use super::{connection, one_msg};
 
+use crate::util::hash::Hasher128;
 use crate::{
     model::{node::Node, tcp::TcpError, SystemHandle},
     HashType,
@@ -135,9 +131,9 @@ impl Process for BebProcess {
     }
 
     fn hash(&self) -> HashType {
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = Hasher128::new();
         self.reg.borrow().iter().for_each(|s| s.hash(&mut hasher));
-        hasher.finish()
+        hasher.finish128()
     }
 }
 
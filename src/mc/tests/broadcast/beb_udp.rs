@@ -1,10 +1,14 @@
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::hash::Hash;
 
 use super::two_msg;
 
 use super::one_msg;
 
-use crate::model::{self, net::send_message};
+use crate::util::hash::Hasher128;
+use crate::{
+    mc,
+    model::{self, net::send_message},
+};
 
 pub use crate::prelude::*;
 
@@ -47,9 +51,9 @@ impl Process for BebProcess {
     }
 
     fn hash(&self) -> model::system::HashType {
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = Hasher128::new();
         self.reg.iter().for_each(|s| s.hash(&mut hasher));
-        hasher.finish()
+        hasher.finish128()
     }
 }
 
@@ -100,6 +104,23 @@ fn one_message_no_faults_with_role() {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+#[test]
+fn one_message_no_faults_reduced() {
+    let cfg = mc::SearchConfigBuilder::no_faults()
+        .max_msg_drops(0)
+        .partial_order_reduction(true)
+        .build();
+    let log_reduced = one_msg::no_drops_with_cfg(build, cfg).unwrap();
+    println!("{}", log_reduced);
+
+    let log = one_msg::no_drops(build).unwrap();
+    println!("{}", log);
+
+    assert!(log_reduced.visited_total < log.visited_total);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 #[should_panic]
 #[test]
 fn one_message_node_crash() {
@@ -118,6 +139,49 @@ fn one_message_udp_drop_bfs() {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+#[test]
+fn one_message_node_crash_reduced() {
+    let cfg = mc::SearchConfigBuilder::new()
+        .max_disk_faults(0)
+        .max_node_faults(1)
+        .max_msg_drops(0)
+        .max_node_shutdown(0)
+        .partial_order_reduction(true)
+        .build();
+    let err_reduced = one_msg::node_crash_after_someone_delivery_with_cfg(build, cfg).unwrap_err();
+    println!("{}", err_reduced.log);
+
+    let err = one_msg::node_crash_after_someone_delivery(build).unwrap_err();
+    println!("{}", err.log);
+
+    assert_eq!(
+        std::mem::discriminant(&err_reduced.kind),
+        std::mem::discriminant(&err.kind)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn one_message_udp_drop_bfs_reduced() {
+    let cfg = mc::SearchConfigBuilder::no_faults()
+        .max_msg_drops(2)
+        .partial_order_reduction(true)
+        .build();
+    let err_reduced = one_msg::udp_drops_bfs_with_cfg(build, cfg).unwrap_err();
+    println!("{}", err_reduced.log);
+
+    let err = one_msg::udp_drops_bfs(build).unwrap_err();
+    println!("{}", err.log);
+
+    assert_eq!(
+        std::mem::discriminant(&err_reduced.kind),
+        std::mem::discriminant(&err.kind)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 #[should_panic]
 #[test]
 fn two_locals_same_node_no_faults_check_causal() {
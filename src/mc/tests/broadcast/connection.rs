@@ -1,12 +1,7 @@
-use std::{
-    cell::RefCell,
-    collections::HashMap,
-    hash::{DefaultHasher, Hash, Hasher},
-    rc::Rc,
-    time::Duration,
-};
+use std::{cell::RefCell, collections::HashMap, hash::Hash, rc::Rc, time::Duration};
 
 pub use crate::prelude::*;
+use crate::util::hash::Hasher128;
 use crate::{
     mc,
     model::{
@@ -115,9 +110,9 @@ impl Process for Broadcast {
     }
 
     fn hash(&self) -> model::system::HashType {
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = Hasher128::new();
         self.con.hash(&mut hasher);
-        hasher.finish()
+        hasher.finish128()
     }
 }
 
@@ -29,6 +29,13 @@ pub fn collect_until_no_events(
 ////////////////////////////////////////////////////////////////////////////////
 
 pub fn no_drops(build: impl BuildFn) -> Result<mc::SearchLog, mc::error::SearchError> {
+    no_drops_with_cfg(build, mc::SearchConfig::no_faults_no_drops())
+}
+
+pub fn no_drops_with_cfg(
+    build: impl BuildFn,
+    cfg: mc::SearchConfig,
+) -> Result<mc::SearchLog, mc::error::SearchError> {
     let nodes = 3;
     let messages = vec!["0:Hello".to_string()];
     let mut checker = mc::ModelChecker::new_with_build(move |s| build(s, nodes));
@@ -39,7 +46,6 @@ pub fn no_drops(build: impl BuildFn) -> Result<mc::SearchLog, mc::error::SearchE
 
     checker.apply(move |s| s.send_local(&"0:bcast".into(), &messages[0]).unwrap());
 
-    let cfg = mc::SearchConfig::no_faults_no_drops();
     let searcher = mc::BfsSearcher::new(cfg);
 
     checker.check(
@@ -54,6 +60,13 @@ pub fn no_drops(build: impl BuildFn) -> Result<mc::SearchLog, mc::error::SearchE
 
 pub fn node_crash_after_someone_delivery(
     build: impl BuildFn,
+) -> Result<mc::SearchLog, mc::error::SearchError> {
+    node_crash_after_someone_delivery_with_cfg(build, mc::SearchConfig::with_node_faults_only(1))
+}
+
+pub fn node_crash_after_someone_delivery_with_cfg(
+    build: impl BuildFn,
+    cfg: mc::SearchConfig,
 ) -> Result<mc::SearchLog, mc::error::SearchError> {
     let nodes = 3;
 
@@ -65,8 +78,7 @@ pub fn node_crash_after_someone_delivery(
 
     checker.apply(|s| s.send_local(&"0:bcast".into(), "0:Hello").unwrap());
 
-    let cfg = mc::SearchConfig::no_faults_no_drops();
-    let searcher = mc::BfsSearcher::new(cfg);
+    let searcher = mc::BfsSearcher::new(mc::SearchConfig::no_faults_no_drops());
     let collect_log = checker.collect(
         |_| Ok(()),
         |_| false,
@@ -80,7 +92,6 @@ pub fn node_crash_after_someone_delivery(
     println!("Collect log:");
     println!("{collect_log}");
 
-    let cfg = mc::SearchConfig::with_node_faults_only(1);
     let searcher = mc::BfsSearcher::new(cfg);
     checker.check(
         |_| Ok(()),
@@ -93,6 +104,13 @@ pub fn node_crash_after_someone_delivery(
 ////////////////////////////////////////////////////////////////////////////////
 
 pub fn udp_drops_bfs(build: impl BuildFn) -> Result<mc::SearchLog, mc::error::SearchError> {
+    udp_drops_bfs_with_cfg(build, mc::SearchConfig::no_faults_with_drops(2))
+}
+
+pub fn udp_drops_bfs_with_cfg(
+    build: impl BuildFn,
+    cfg: mc::SearchConfig,
+) -> Result<mc::SearchLog, mc::error::SearchError> {
     let nodes = 3;
     let messages = vec!["0:Hello".to_string()];
     let mut checker = mc::ModelChecker::new_with_build(move |s| build(s, nodes));
@@ -103,7 +121,6 @@ pub fn udp_drops_bfs(build: impl BuildFn) -> Result<mc::SearchLog, mc::error::Se
 
     checker.apply(move |s| s.send_local(&"0:bcast".into(), &messages[0]).unwrap());
 
-    let cfg = mc::SearchConfig::no_faults_with_drops(2);
     let searcher = mc::BfsSearcher::new(cfg);
 
     checker.check(
@@ -117,6 +134,13 @@ pub fn udp_drops_bfs(build: impl BuildFn) -> Result<mc::SearchLog, mc::error::Se
 ////////////////////////////////////////////////////////////////////////////////
 
 pub fn udp_drops_dfs(build: impl BuildFn) -> Result<mc::SearchLog, mc::error::SearchError> {
+    udp_drops_dfs_with_cfg(build, mc::SearchConfig::no_faults_with_drops(2))
+}
+
+pub fn udp_drops_dfs_with_cfg(
+    build: impl BuildFn,
+    cfg: mc::SearchConfig,
+) -> Result<mc::SearchLog, mc::error::SearchError> {
     let nodes = 3;
     let messages = vec!["0:Hello".to_string()];
     let mut checker = mc::ModelChecker::new_with_build(move |s| build(s, nodes));
@@ -127,7 +151,6 @@ pub fn udp_drops_dfs(build: impl BuildFn) -> Result<mc::SearchLog, mc::error::Se
 
     checker.apply(move |s| s.send_local(&"0:bcast".into(), &messages[0]).unwrap());
 
-    let cfg = mc::SearchConfig::no_faults_with_drops(2);
     let searcher = mc::DfsSearcher::new(cfg);
 
     checker.check(
@@ -1,13 +1,9 @@
-use std::{
-    cell::RefCell,
-    collections::BTreeSet,
-    hash::{DefaultHasher, Hash, Hasher},
-    rc::Rc,
-};
+use std::{cell::RefCell, collections::BTreeSet, hash::Hash, rc::Rc};
 
 use serde::{Deserialize, Serialize};
 
 pub use crate::prelude::*;
+use crate::util::hash::Hasher128;
 use crate::{
     mc::tests::broadcast::{one_msg, two_msg},
     model,
@@ -127,9 +123,9 @@ impl Process for Bcast {
     }
 
     fn hash(&self) -> model::system::HashType {
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = Hasher128::new();
         self.state.borrow().reg.borrow().hash(&mut hasher);
-        hasher.finish()
+        hasher.finish128()
     }
 }
 
@@ -1,11 +1,12 @@
 use std::{
     cell::RefCell,
     collections::{BTreeMap, BTreeSet},
-    hash::{DefaultHasher, Hash, Hasher},
+    hash::Hash,
     rc::Rc,
 };
 
 pub use crate::prelude::*;
+use crate::util::hash::Hasher128;
 use crate::{
     mc::tests::broadcast::{connection, one_msg, two_msg},
     model::{self, TcpSender},
@@ -163,9 +164,9 @@ impl Process for Bcast {
     }
 
     fn hash(&self) -> model::system::HashType {
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = Hasher128::new();
         self.state.borrow().reg.borrow().hash(&mut hasher);
-        hasher.finish()
+        hasher.finish128()
     }
 }
 
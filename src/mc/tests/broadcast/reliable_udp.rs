@@ -1,13 +1,17 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
-    hash::{DefaultHasher, Hash, Hasher},
+    hash::Hash,
 };
 
 use serde::{Deserialize, Serialize};
 
 pub use crate::prelude::*;
+use crate::util::hash::Hasher128;
 use crate::{
-    mc::tests::broadcast::{one_msg, two_msg},
+    mc::{
+        self,
+        tests::broadcast::{one_msg, two_msg},
+    },
     model::{self, net::send_message},
 };
 
@@ -97,14 +101,14 @@ impl Process for Bcast {
     }
 
     fn hash(&self) -> model::system::HashType {
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = Hasher128::new();
         let mut pending = self.pending.iter().collect::<Vec<_>>();
         pending.sort();
         for (msg, acks) in pending.into_iter() {
             msg.hash(&mut hasher);
             acks.min(&self.proc_cnt.div_ceil(2)).hash(&mut hasher);
         }
-        hasher.finish()
+        hasher.finish128()
     }
 }
 
@@ -157,6 +161,46 @@ fn one_message_udp_drop_dfs() {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+#[test]
+fn one_message_udp_drop_bfs_reduced() {
+    let cfg = mc::SearchConfigBuilder::no_faults()
+        .max_msg_drops(2)
+        .partial_order_reduction(true)
+        .build();
+    let err_reduced = one_msg::udp_drops_bfs_with_cfg(build, cfg).unwrap_err();
+    println!("{}", err_reduced.log);
+
+    let err = one_msg::udp_drops_bfs(build).unwrap_err();
+    println!("{}", err.log);
+
+    assert_eq!(
+        std::mem::discriminant(&err_reduced.kind),
+        std::mem::discriminant(&err.kind)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn one_message_udp_drop_dfs_reduced() {
+    let cfg = mc::SearchConfigBuilder::no_faults()
+        .max_msg_drops(2)
+        .partial_order_reduction(true)
+        .build();
+    let err_reduced = one_msg::udp_drops_dfs_with_cfg(build, cfg).unwrap_err();
+    println!("{}", err_reduced.log);
+
+    let err = one_msg::udp_drops_dfs(build).unwrap_err();
+    println!("{}", err.log);
+
+    assert_eq!(
+        std::mem::discriminant(&err_reduced.kind),
+        std::mem::discriminant(&err.kind)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 #[test]
 fn two_concurrent_messages_with_faults() {
     let log = two_msg::concurrent_with_faults_check_validity_and_agreement(build).unwrap();
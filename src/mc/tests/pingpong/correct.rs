@@ -97,6 +97,11 @@ impl Process for Pong {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
     use crate::mc::{
         self,
         tests::pingpong::common::{make_build, make_goal, make_invariant},
@@ -191,6 +196,168 @@ mod tests {
 
     ////////////////////////////////////////////////////////////////////////////////
 
+    #[test]
+    fn parallel_same_as_bfs() {
+        let locals = 2;
+        let max_drops = 1;
+
+        let invariant = make_invariant(locals);
+        let prune = |_| false;
+        let goal = make_goal(locals);
+        let build = make_build(
+            Duration::from_millis(100),
+            Duration::from_millis(600),
+            || {
+                Rc::new(RefCell::new(Ping::new(
+                    Address::new("n2", "pong"),
+                    Duration::from_secs(1),
+                )))
+            },
+            || Rc::new(RefCell::new(Pong::new())),
+            locals,
+        );
+
+        let cfg = mc::SearchConfigBuilder::no_faults()
+            .max_msg_drops(max_drops)
+            .max_node_faults(0)
+            .build();
+
+        let checked_bfs = {
+            let searcher = mc::BfsSearcher::new(cfg.clone());
+            let checker = mc::ModelChecker::new_with_build(build.clone());
+            checker
+                .check(invariant.clone(), prune, goal.clone(), searcher)
+                .unwrap()
+        };
+
+        let checked_parallel = {
+            let searcher = mc::ParallelSearcher::new(cfg, 4);
+            let checker = mc::ModelChecker::new_with_build(build);
+            checker.check(invariant, prune, goal, searcher).unwrap()
+        };
+
+        assert_eq!(checked_bfs.visited_total, checked_parallel.visited_total);
+        assert_eq!(checked_bfs.visited_unique, checked_parallel.visited_unique);
+    }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn parallel_skips_visited() {
+        let locals = 2;
+        let build = make_build(
+            Duration::from_millis(100),
+            Duration::from_millis(600),
+            || {
+                Rc::new(RefCell::new(Ping::new(
+                    Address::new("n2", "pong"),
+                    Duration::from_secs(1),
+                )))
+            },
+            || Rc::new(RefCell::new(Pong::new())),
+            locals,
+        );
+        let cfg = mc::SearchConfigBuilder::no_faults()
+            .max_msg_drops(1)
+            .max_node_faults(0)
+            .build();
+
+        // goal is checked only in the states, which are not visited before
+        let counting_goal = |calls: Arc<AtomicUsize>| {
+            let goal = make_goal(locals);
+            move |s: mc::StateView| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                goal(s)
+            }
+        };
+
+        let bfs_calls = Arc::new(AtomicUsize::new(0));
+        let bfs_log = mc::ModelChecker::new_with_build(build.clone())
+            .collect(
+                |_| Ok(()),
+                |_| false,
+                counting_goal(bfs_calls.clone()),
+                mc::BfsSearcher::new(cfg.clone()),
+            )
+            .unwrap();
+        assert!(bfs_log.visited_total > bfs_log.visited_unique);
+
+        // duplicates on the same level are skipped in the order of the level
+        // if there is the only worker
+        for workers in [1, 4] {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let log = mc::ModelChecker::new_with_build(build.clone())
+                .collect(
+                    |_| Ok(()),
+                    |_| false,
+                    counting_goal(calls.clone()),
+                    mc::ParallelSearcher::new(cfg.clone(), workers),
+                )
+                .unwrap();
+            assert_eq!(bfs_log.visited_unique, log.visited_unique);
+            let calls = calls.load(Ordering::Relaxed);
+            if workers == 1 {
+                assert_eq!(bfs_calls.load(Ordering::Relaxed), calls);
+            }
+            assert!(calls < log.visited_total);
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn snapshots_same_as_replay() {
+        let locals = 2;
+
+        let invariant = make_invariant(locals);
+        let prune = |_| false;
+        let goal = make_goal(locals);
+        let build = make_build(
+            Duration::from_millis(100),
+            Duration::from_millis(600),
+            || {
+                Rc::new(RefCell::new(Ping::new(
+                    Address::new("n2", "pong"),
+                    Duration::from_secs(1),
+                )))
+            },
+            || Rc::new(RefCell::new(Pong::new())),
+            locals,
+        );
+
+        let cfg = mc::SearchConfigBuilder::no_faults().max_msg_drops(1);
+        let replay_cfg = cfg.clone().build();
+        let snapshot_cfg = cfg.max_snapshots(16).build();
+
+        let check_bfs = |cfg| {
+            let searcher = mc::BfsSearcher::new(cfg);
+            let checker = mc::ModelChecker::new_with_build(build.clone());
+            checker
+                .check(invariant.clone(), prune, goal.clone(), searcher)
+                .unwrap()
+        };
+        let replay = check_bfs(replay_cfg.clone());
+        let snapshot = check_bfs(snapshot_cfg.clone());
+        assert_eq!(replay.visited_total, snapshot.visited_total);
+        assert_eq!(replay.visited_unique, snapshot.visited_unique);
+        assert!(snapshot.replayed < replay.replayed);
+
+        let check_dfs = |cfg| {
+            let searcher = mc::DfsSearcher::new(cfg);
+            let checker = mc::ModelChecker::new_with_build(build.clone());
+            checker
+                .check(invariant.clone(), prune, goal.clone(), searcher)
+                .unwrap()
+        };
+        let replay = check_dfs(replay_cfg);
+        let snapshot = check_dfs(snapshot_cfg);
+        assert_eq!(replay.visited_total, snapshot.visited_total);
+        assert_eq!(replay.visited_unique, snapshot.visited_unique);
+        assert!(snapshot.replayed < replay.replayed);
+    }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
     #[test]
     fn on_node_crash() {
         let locals = 2;
@@ -254,7 +421,7 @@ mod tests {
         let searcher = mc::BfsSearcher::new(cfg.clone());
         let mut checker = mc::ModelChecker::new_with_build(build);
         let log = checker
-            .collect(invariant.clone(), prune.clone(), goal, searcher)
+            .collect(invariant.clone(), prune, goal, searcher)
             .unwrap();
         assert!(log.visited_unique > 0);
         println!("{}", log);
@@ -61,10 +61,19 @@ impl Process for Pong {
 #[cfg(test)]
 mod tests {
 
-    use std::{cell::RefCell, rc::Rc, time::Duration};
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
 
     use crate::mc::{
         self,
+        search::step::StateTraceStep,
         tests::pingpong::common::{make_build, make_goal, make_invariant},
     };
 
@@ -126,4 +135,281 @@ mod tests {
         assert!(check_result.is_err());
         println!("{}", check_result.unwrap_err());
     }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn search_log() {
+        let locals = 2;
+
+        let build = make_build(
+            Duration::from_millis(100),
+            Duration::from_millis(600),
+            || Rc::new(RefCell::new(Ping::new(Address::new("n2", "pong")))),
+            || Rc::new(RefCell::new(Pong::new())),
+            locals,
+        );
+
+        let reports = Arc::new(AtomicUsize::new(0));
+        let reported = reports.clone();
+        let cfg = mc::SearchConfigBuilder::no_faults()
+            .max_msg_drops(1)
+            .progress(Duration::ZERO, move |_| {
+                reported.fetch_add(1, Ordering::SeqCst);
+            })
+            .build();
+        let mut checker = mc::ModelChecker::new_with_build(build);
+        let prune = |v: mc::StateView| v.depth() > 3;
+        let goal = |_| Err("never".to_string());
+        let log = checker
+            .collect(
+                make_invariant(locals),
+                prune,
+                goal,
+                mc::DfsSearcher::new(cfg),
+            )
+            .unwrap();
+
+        assert_eq!(log.max_depth, 4);
+        assert!(log.pruned > 0);
+        assert!(log.faults["DropUdp"] > 0);
+        assert!(log.branching_factor("SelectUdp") > 0.0);
+        assert_eq!(log.branching_factor("CrashNode"), 0.0);
+        assert_eq!(log.frontier.len(), log.visited_total);
+        assert_eq!(reports.load(Ordering::SeqCst), log.visited_total);
+        println!("{}", log);
+    }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn unreliable_net_parallel() {
+        let locals = 2;
+        let max_drops = 2;
+
+        let invariant = make_invariant(locals);
+        let prune = |_| false;
+        let goal = make_goal(locals);
+        let build = make_build(
+            Duration::from_millis(100),
+            Duration::from_millis(600),
+            || Rc::new(RefCell::new(Ping::new(Address::new("n2", "pong")))),
+            || Rc::new(RefCell::new(Pong::new())),
+            locals,
+        );
+
+        let cfg = mc::SearchConfigBuilder::no_faults()
+            .max_msg_drops(max_drops)
+            .build();
+
+        let bfs_err = {
+            let searcher = mc::BfsSearcher::new(cfg.clone());
+            let checker = mc::ModelChecker::new_with_build(build.clone());
+            checker
+                .check(invariant.clone(), prune, goal.clone(), searcher)
+                .unwrap_err()
+        };
+
+        let parallel_err = {
+            let searcher = mc::ParallelSearcher::new(cfg, 3);
+            let checker = mc::ModelChecker::new_with_build(build);
+            checker.check(invariant, prune, goal, searcher).unwrap_err()
+        };
+
+        assert_eq!(bfs_err.to_string(), parallel_err.to_string());
+    }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn random_walk() {
+        let locals = 2;
+
+        let invariant = make_invariant(locals);
+        let prune = |_| false;
+        let goal = make_goal(locals);
+        let build = make_build(
+            Duration::from_millis(100),
+            Duration::from_millis(600),
+            || Rc::new(RefCell::new(Ping::new(Address::new("n2", "pong")))),
+            || Rc::new(RefCell::new(Pong::new())),
+            locals,
+        );
+
+        // reliable network
+        let cfg = mc::SearchConfigBuilder::no_faults()
+            .max_msg_drops(0)
+            .max_depth(100)
+            .build();
+        let searcher = mc::RandomWalkSearcher::new(cfg, 123, 20);
+        let checker = mc::ModelChecker::new_with_build(build.clone());
+        let checked = checker
+            .check(invariant.clone(), prune, goal.clone(), searcher)
+            .unwrap();
+        assert!(checked.visited_total >= 20);
+
+        // unreliable network, the same seed gives the same trace
+        let cfg = mc::SearchConfigBuilder::no_faults()
+            .max_msg_drops(2)
+            .max_depth(100)
+            .build();
+        let errors: Vec<_> = (0..2)
+            .map(|_| {
+                let searcher = mc::RandomWalkSearcher::new(cfg.clone(), 123, 100);
+                let checker = mc::ModelChecker::new_with_build(build.clone());
+                checker
+                    .check(invariant.clone(), prune, goal.clone(), searcher)
+                    .unwrap_err()
+            })
+            .collect();
+        assert!(matches!(
+            errors[0].kind,
+            mc::error::SearchErrorKind::LivenessViolation(..)
+        ));
+        assert_eq!(errors[0].to_string(), errors[1].to_string());
+    }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn partitioned_net() {
+        let locals = 2;
+
+        let invariant = make_invariant(locals);
+        let prune = |_| false;
+        let goal = make_goal(locals);
+        let build = make_build(
+            Duration::from_millis(100),
+            Duration::from_millis(600),
+            || Rc::new(RefCell::new(Ping::new(Address::new("n2", "pong")))),
+            || Rc::new(RefCell::new(Pong::new())),
+            locals,
+        );
+
+        let cfg = mc::SearchConfigBuilder::no_faults()
+            .max_msg_drops(0)
+            .max_partitions(1)
+            .build();
+        let searcher = mc::BfsSearcher::new(cfg);
+        let checker = mc::ModelChecker::new_with_build(build);
+        let err = checker.check(invariant, prune, goal, searcher).unwrap_err();
+        println!("{}", err);
+
+        // messages are dropped while n1 is separated from n2
+        let mc::error::SearchErrorKind::LivenessViolation(violation) = &err.kind else {
+            panic!("unexpected error: {}", err);
+        };
+        let steps = violation.trace.steps();
+        assert_eq!(steps.len(), 3);
+        assert!(matches!(&steps[1], StateTraceStep::Partition(nodes) if nodes == &[1]));
+        assert!(matches!(&steps[2], StateTraceStep::Heal));
+        assert_eq!(err.log.faults.get("Partition"), Some(&3));
+    }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn same_errors_reduced() {
+        let locals = 2;
+
+        let invariant = make_invariant(locals);
+        let prune = |_| false;
+        let goal = make_goal(locals);
+        let build = make_build(
+            Duration::from_millis(100),
+            Duration::from_millis(600),
+            || Rc::new(RefCell::new(Ping::new(Address::new("n2", "pong")))),
+            || Rc::new(RefCell::new(Pong::new())),
+            locals,
+        );
+
+        let check = |cfg: mc::SearchConfigBuilder, reduced: bool, dfs: bool| {
+            let cfg = cfg.partial_order_reduction(reduced).build();
+            let checker = mc::ModelChecker::new_with_build(build.clone());
+            let result = if dfs {
+                let searcher = mc::DfsSearcher::new(cfg);
+                checker.check(invariant.clone(), prune, goal.clone(), searcher)
+            } else {
+                let searcher = mc::BfsSearcher::new(cfg);
+                checker.check(invariant.clone(), prune, goal.clone(), searcher)
+            };
+            result.unwrap_err().kind
+        };
+
+        // unreliable and partitioned networks
+        let configs = [
+            mc::SearchConfigBuilder::no_faults().max_msg_drops(2),
+            mc::SearchConfigBuilder::no_faults()
+                .max_msg_drops(0)
+                .max_partitions(1),
+        ];
+        for cfg in configs {
+            for dfs in [false, true] {
+                let err = check(cfg.clone(), false, dfs);
+                let err_reduced = check(cfg.clone(), true, dfs);
+                assert_eq!(
+                    std::mem::discriminant(&err_reduced),
+                    std::mem::discriminant(&err)
+                );
+            }
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn save_and_replay() {
+        let locals = 2;
+        let max_drops = 2;
+
+        let invariant = make_invariant(locals);
+        let prune = |_| false;
+        let goal = make_goal(locals);
+        let build = make_build(
+            Duration::from_millis(100),
+            Duration::from_millis(600),
+            || Rc::new(RefCell::new(Ping::new(Address::new("n2", "pong")))),
+            || Rc::new(RefCell::new(Pong::new())),
+            locals,
+        );
+
+        let cfg = mc::SearchConfigBuilder::no_faults()
+            .max_msg_drops(max_drops)
+            .build();
+        let searcher = mc::BfsSearcher::new(cfg);
+        let checker = mc::ModelChecker::new_with_build(build.clone());
+        let err = checker
+            .check(invariant.clone(), prune, goal.clone(), searcher)
+            .unwrap_err();
+
+        let path =
+            std::env::temp_dir().join(format!("dsbuild-mc-replay-{}.json", std::process::id()));
+        err.save_trace(&path).unwrap();
+        let trace = err.kind.trace().unwrap();
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(json, trace.to_json());
+        assert!(json.contains("\"build\""));
+
+        // goal is not achieved in the last state of the trace
+        let checker = mc::ModelChecker::new_with_build(build.clone());
+        assert!(checker.replay(&path, invariant).unwrap().is_none());
+        let replayed = checker.replay(&path, goal).unwrap();
+        assert!(matches!(
+            replayed,
+            Some(mc::error::SearchErrorKind::InvariantViolation(..))
+        ));
+
+        // build function is referenced by its name
+        let mut checker = mc::ModelChecker::new_with_build(|_| {});
+        checker.apply_named("other", build);
+        let json = json.replace("\"build\"", "\"missing\"");
+        std::fs::write(&path, json).unwrap();
+        let replayed = checker.replay(&path, |_| Ok(())).unwrap_err();
+        assert!(matches!(
+            replayed,
+            mc::error::ReplayError::UnknownApply(name) if name == "missing"
+        ));
+
+        std::fs::remove_file(path).unwrap();
+    }
 }
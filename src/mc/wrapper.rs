@@ -8,6 +8,7 @@ pub struct ApplyFnWrapper<F>
 where
     F: control::ApplyFn,
 {
+    name: String,
     inner: F,
 }
 
@@ -15,8 +16,11 @@ impl<F> ApplyFnWrapper<F>
 where
     F: control::ApplyFn,
 {
-    pub fn new(inner: F) -> Self {
-        Self { inner }
+    pub fn new(name: impl Into<String>, inner: F) -> Self {
+        Self {
+            name: name.into(),
+            inner,
+        }
     }
 }
 
@@ -30,8 +34,13 @@ where
 
     fn clone(&self) -> Box<dyn control::ApplyFunctor> {
         let clone = Self {
+            name: self.name.clone(),
             inner: self.inner.clone(),
         };
         Box::new(clone)
     }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
 }
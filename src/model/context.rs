@@ -57,6 +57,16 @@ impl Context {
 
     ////////////////////////////////////////////////////////////////////////////////
 
+    pub fn choose(&self, n: usize) -> usize {
+        self.event_manager.choose(self.proc.clone(), n)
+    }
+
+    pub fn random_seed(&self) -> u64 {
+        self.event_manager.random_seed(self.proc.clone())
+    }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
     pub fn spawn<F>(&self, task: F) -> JoinHandle<F::Output>
     where
         F: Future + 'static,
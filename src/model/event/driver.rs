@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use crate::{model::HashType, Address};
+
 use super::Event;
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -9,5 +11,9 @@ pub trait EventDriver {
 
     fn cancel_event(&mut self, event: &Event);
 
-    fn hash_pending(&self) -> u64;
+    fn hash_pending(&self) -> HashType;
+
+    fn choose(&mut self, n: usize) -> usize;
+
+    fn random_seed(&mut self, proc: &Address) -> u64;
 }
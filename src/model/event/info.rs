@@ -1,5 +1,7 @@
 use std::{fmt::Display, hash::Hash, time::Duration};
 
+use serde::{Deserialize, Serialize};
+
 use crate::model::{
     fs::event::{FsEventKind, FsEventOutcome},
     proc::ProcessHandle,
@@ -121,7 +123,7 @@ impl Display for RpcMessage {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub enum RpcEventKind {
     ConnectionRefused,
 }
@@ -157,7 +159,7 @@ impl Display for RpcEvent {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TcpEventKind {
     SenderDropped,
     #[allow(unused)]
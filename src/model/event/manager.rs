@@ -14,15 +14,20 @@ use crate::{
             driver::EventDriver,
             info::{RpcMessage, RpcMessageKind, TcpMessage},
         },
-        fs::{event::FsEvent, registry::FsEventRegistry},
+        fs::{
+            event::{FsEvent, FsEventOutcome},
+            registry::FsEventRegistry,
+        },
         hash::HashContext,
         log::{
-            FutureFellAsleep, FutureWokeUp, Log, LogEntry, NodeCrashed, NodeShutdown, ProcessInfo,
-            ProcessReceivedLocalMessage, ProcessSentLocalMessage, RpcMessageDropped,
+            FsCrashed, FutureFellAsleep, FutureWokeUp, Log, LogEntry, NetworkHealed,
+            NetworkPartitioned, NodeCrashed, NodeRestarted, NodeShutdown, ProcessChose,
+            ProcessInfo, ProcessReceivedLocalMessage, ProcessSentLocalMessage, RpcMessageDropped,
             RpcMessageReceived, RpcMessageSent, TcpMessageDropped, TcpMessageReceived,
             TcpMessageSent, TimerCancelled, TimerFired, TimerSet, UdpMessageDropped,
             UdpMessageReceived, UdpMessageSent,
         },
+        net::PartitionPolicy,
         proc::{time, ProcessHandle, ProcessState},
         rpc::{RpcListener, RpcManager, RpcRegistry, RpcRequest, RpcResponse},
         runtime::{JoinHandle, RuntimeHandle},
@@ -37,7 +42,7 @@ use crate::{
         SystemHandle,
     },
     util::{
-        oneshot,
+        self, oneshot,
         trigger::{self, make_trigger, Trigger},
     },
     Address, Process, RpcResult,
@@ -67,13 +72,16 @@ pub struct EventManagerState {
     rpc: Rc<RefCell<RpcManager>>,
     timers: TimerManager,
     time: Duration,
+    // network events held by the partition
+    held: Vec<Event>,
 }
 
 impl EventManagerState {
     fn hash(&self, ctx: HashContext) -> HashType {
         let h1 = ctx.hash_events(self.unhandled_events.iter().map(|e| &self.events[*e]));
         let h2 = self.driver.upgrade().unwrap().borrow().hash_pending();
-        h1 ^ h2
+        let h3 = ctx.hash_events(self.held.iter());
+        util::hash::hash_list([h1, h2, h3].into_iter())
     }
 
     ////////////////////////////////////////////////////////////////////////////////
@@ -124,49 +132,144 @@ impl EventManagerState {
                 .borrow_mut()
                 .cancel_event(event);
             let remove_result = self.unhandled_events.remove(&event.id);
-            match &event.info {
-                EventInfo::UdpMessage(msg) => {
-                    let entry = UdpMessageDropped {
-                        from: msg.from.address(),
-                        to: msg.to.address(),
-                        content: msg.content.clone(),
-                        time: self.time(),
-                    };
-                    self.event_log
-                        .borrow_mut()
-                        .add_entry(LogEntry::UdpMessageDropped(entry));
+            self.log_event_dropped(event);
+            assert!(remove_result);
+        }
+
+        // held events are cancelled too
+        let (cancelled, held): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.held).into_iter().partition(&pred);
+        self.held = held;
+        cancelled.iter().for_each(|e| self.log_event_dropped(e));
+    }
+
+    fn log_event_dropped(&self, event: &Event) {
+        let entry = match &event.info {
+            EventInfo::UdpMessage(msg) => {
+                let entry = UdpMessageDropped {
+                    from: msg.from.address(),
+                    to: msg.to.address(),
+                    content: msg.content.clone(),
+                    time: self.time(),
+                };
+                LogEntry::UdpMessageDropped(entry)
+            }
+            EventInfo::TcpMessage(msg) => {
+                let entry = TcpMessageDropped {
+                    from: msg.from.address(),
+                    to: msg.to.address(),
+                    packet: msg.packet.clone(),
+                    time: self.time(),
+                };
+                LogEntry::TcpMessageDropped(entry)
+            }
+            EventInfo::RpcMessage(msg) => {
+                let entry = RpcMessageDropped {
+                    from: msg.from.address(),
+                    to: msg.to.address(),
+                    content: match &msg.kind {
+                        RpcMessageKind::Request { content, .. } => content.clone(),
+                        RpcMessageKind::Response { content, .. } => {
+                            content.clone().unwrap_or("failure".into())
+                        }
+                    },
+                    time: self.time(),
+                };
+                LogEntry::RpcMessageDropped(entry)
+            }
+            _ => return,
+        };
+        self.event_log.borrow_mut().add_entry(entry);
+    }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
+    /// Registers network event taking into account the network partition.
+    /// Event between separated nodes is dropped, held or replaced
+    /// with the connection refused event according to the partition policy.
+    fn register_net_event(&mut self, mut event: Event) {
+        event.id = self.events.len();
+        event.time = self.time();
+
+        let net = self.system().network();
+        let separated = net_event_ends(&event)
+            .map(|(from, to)| !net.connected(&from.node, &to.node))
+            .unwrap_or(false);
+        if separated {
+            match (net.partition_policy(), event.info.clone()) {
+                (_, EventInfo::UdpMessage(_)) => {
+                    self.log_event_dropped(&event);
+                    return;
+                }
+                (PartitionPolicy::Hang, _) => {
+                    self.held.push(event);
+                    return;
                 }
-                EventInfo::TcpMessage(msg) => {
-                    let entry = TcpMessageDropped {
-                        from: msg.from.address(),
-                        to: msg.to.address(),
-                        packet: msg.packet.clone(),
-                        time: self.time(),
+                (PartitionPolicy::Refuse, EventInfo::TcpMessage(msg)) => {
+                    self.log_event_dropped(&event);
+                    let refused = TcpEvent {
+                        kind: TcpEventKind::ConnectionRefused,
+                        to: msg.from,
                     };
-                    self.event_log
-                        .borrow_mut()
-                        .add_entry(LogEntry::TcpMessageDropped(entry));
+                    event.info = EventInfo::TcpEvent(refused);
                 }
-                EventInfo::RpcMessage(msg) => {
-                    let entry = RpcMessageDropped {
-                        from: msg.from.address(),
-                        to: msg.to.address(),
-                        content: match &msg.kind {
-                            RpcMessageKind::Request { content, .. } => content.clone(),
-                            RpcMessageKind::Response { content, .. } => {
-                                content.clone().unwrap_or("failure".into())
-                            }
-                        },
-                        time: self.time(),
+                (PartitionPolicy::Refuse, EventInfo::RpcMessage(msg)) => {
+                    // failure is reported to the waiting side
+                    let to = match msg.kind {
+                        RpcMessageKind::Request { .. } => msg.from,
+                        RpcMessageKind::Response { .. } => msg.to,
+                    };
+                    let refused = RpcEvent {
+                        kind: RpcEventKind::ConnectionRefused,
+                        to,
                     };
-                    self.event_log
-                        .borrow_mut()
-                        .add_entry(LogEntry::RpcMessageDropped(entry));
+                    event.info = EventInfo::RpcEvent(refused);
                 }
-                _ => {}
+                _ => unreachable!(),
             }
-            assert!(remove_result);
         }
+
+        let (min_delay, max_delay) = net.delays_range();
+        self.register_event(&event, min_delay, max_delay);
+        self.events.push(event);
+    }
+
+    /// Reroutes held events and in-flight events between separated nodes
+    /// after the network partition changed.
+    fn reroute_net_events(&mut self) {
+        let net = self.system().network();
+        let separated = self
+            .unhandled_events
+            .iter()
+            .cloned()
+            .filter(|id| {
+                net_event_ends(&self.events[*id])
+                    .map(|(from, to)| !net.connected(&from.node, &to.node))
+                    .unwrap_or(false)
+            })
+            .collect::<Vec<_>>();
+        let mut events = std::mem::take(&mut self.held);
+        for id in separated {
+            self.driver
+                .upgrade()
+                .expect("can not upgrade driver")
+                .borrow_mut()
+                .cancel_event(&self.events[id]);
+            self.unhandled_events.remove(&id);
+            events.push(self.events[id].cloned());
+        }
+        events
+            .into_iter()
+            .for_each(|event| self.register_net_event(event));
+    }
+}
+
+fn net_event_ends(event: &Event) -> Option<(Address, Address)> {
+    match &event.info {
+        EventInfo::UdpMessage(msg) => Some((msg.from.address(), msg.to.address())),
+        EventInfo::TcpMessage(msg) => Some((msg.from.address(), msg.to.address())),
+        EventInfo::RpcMessage(msg) => Some((msg.from.address(), msg.to.address())),
+        _ => None,
     }
 }
 
@@ -191,6 +294,7 @@ impl EventManager {
             rpc: Default::default(),
             timers: Default::default(),
             time: Duration::ZERO,
+            held: Default::default(),
         };
         Self(Rc::new(RefCell::new(state)))
     }
@@ -317,6 +421,78 @@ impl EventManagerHandle {
         self.state().borrow_mut().stat.nodes_shutdown += 1;
     }
 
+    pub fn on_node_restart(&self, node: &str) {
+        // add log entry
+        let entry = NodeRestarted {
+            node: node.to_string(),
+            time: self.time(),
+        };
+        let entry = LogEntry::NodeRestarted(entry);
+        self.state()
+            .borrow_mut()
+            .event_log
+            .borrow_mut()
+            .add_entry(entry);
+
+        self.state().borrow_mut().stat.nodes_restarted += 1;
+    }
+
+    pub fn on_network_partition(&self, groups: Vec<Vec<String>>) {
+        let state = self.state();
+        let mut state = state.borrow_mut();
+
+        // add log entry
+        let entry = NetworkPartitioned {
+            groups,
+            time: state.time(),
+        };
+        let entry = LogEntry::NetworkPartitioned(entry);
+        state.event_log.borrow_mut().add_entry(entry);
+
+        state.reroute_net_events();
+        state.stat.network_partitions += 1;
+    }
+
+    pub fn on_network_heal(&self) {
+        let state = self.state();
+        let mut state = state.borrow_mut();
+
+        // add log entry
+        let entry = NetworkHealed { time: state.time() };
+        let entry = LogEntry::NetworkHealed(entry);
+        state.event_log.borrow_mut().add_entry(entry);
+
+        state.reroute_net_events();
+    }
+
+    pub fn on_fs_crash(&self, node: &str) {
+        // add log entry
+        let entry = FsCrashed {
+            node: node.to_string(),
+            time: self.time(),
+        };
+        let entry = LogEntry::FsCrashed(entry);
+        self.state()
+            .borrow_mut()
+            .event_log
+            .borrow_mut()
+            .add_entry(entry);
+
+        let fs_event = |e: &Event| matches!(&e.info, EventInfo::FsEvent(e) if e.proc.node == node);
+        self.cancel_events(fs_event);
+
+        // pending requests fail after their triggers are dropped
+        let state = self.state();
+        let mut state = state.borrow_mut();
+        state
+            .events
+            .iter_mut()
+            .filter(|e| fs_event(e))
+            .for_each(|e| drop(e.on_happen.take()));
+
+        state.stat.disk_faults += 1;
+    }
+
     pub fn add_log(&self, process: ProcessHandle, content: String) {
         let state = self.state();
         let state = state.borrow_mut();
@@ -330,6 +506,40 @@ impl EventManagerHandle {
         state.event_log.borrow_mut().add_entry(entry);
     }
 
+    pub fn choose(&self, process: ProcessHandle, n: usize) -> usize {
+        assert!(n > 0, "can not choose from zero options");
+        let state = self.state();
+        let state = state.borrow_mut();
+        let value = state
+            .driver
+            .upgrade()
+            .expect("can not upgrade driver")
+            .borrow_mut()
+            .choose(n);
+        assert!(value < n);
+        let entry = ProcessChose {
+            process: process.address(),
+            time: state.time(),
+            value,
+            options: n,
+        };
+        state
+            .event_log
+            .borrow_mut()
+            .add_entry(LogEntry::ProcessChose(entry));
+        value
+    }
+
+    pub fn random_seed(&self, process: ProcessHandle) -> u64 {
+        self.state()
+            .borrow()
+            .driver
+            .upgrade()
+            .expect("can not upgrade driver")
+            .borrow_mut()
+            .random_seed(&process.address())
+    }
+
     pub fn stat(&self) -> EventStat {
         self.state().borrow().stat.clone()
     }
@@ -339,6 +549,12 @@ impl EventManagerHandle {
         self.state().borrow().unhandled_events.len()
     }
 
+    /// Returns the number of events registered so far,
+    /// which is also the id of the next registered event.
+    pub fn events_count(&self) -> usize {
+        self.state().borrow().events.len()
+    }
+
     ////////////////////////////////////////////////////////////////////////////////
     // Register events
     ////////////////////////////////////////////////////////////////////////////////
@@ -382,8 +598,6 @@ impl EventManagerHandle {
             content,
         };
         let info = EventInfo::UdpMessage(info);
-        let net = system_handle.network();
-        let (shift_min, shift_max) = net.delays_range();
         let event = Event {
             id: state.events.len(),
             time: state.time(),
@@ -392,8 +606,7 @@ impl EventManagerHandle {
         };
 
         // register event
-        state.register_event(&event, shift_min, shift_max);
-        state.events.push(event);
+        state.register_net_event(event);
     }
 
     ////////////////////////////////////////////////////////////////////////////////
@@ -462,6 +675,13 @@ impl EventManagerHandle {
             EventOutcomeKind::FsEventHappen(outcome) => {
                 let _ = event.on_happen.unwrap().invoke(outcome.clone());
             }
+            EventOutcomeKind::FsEventFailed(err) => {
+                self.state().borrow_mut().stat.disk_faults += 1;
+                let _ = event
+                    .on_happen
+                    .unwrap()
+                    .invoke::<FsEventOutcome>(Err(err.clone()));
+            }
             EventOutcomeKind::RpcMessageDelivered => {
                 let _ = event.on_happen.unwrap().invoke::<RpcResult<()>>(Ok(()));
             }
@@ -638,23 +858,20 @@ impl EventManagerState {
         to: ProcessHandle,
         packet: &TcpPacket,
         trigger: Trigger,
-    ) -> &Event {
+    ) {
         let msg = TcpMessage {
             tcp_msg_id: self.inc_tcp_msg_id(),
             from,
             to,
             packet: packet.clone(),
         };
-        let (min_shift, max_shift) = self.system().network().delays_range();
         let event = Event {
             id: self.events.len(),
             time: self.time(),
             info: EventInfo::TcpMessage(msg),
             on_happen: Some(trigger),
         };
-        self.register_event(&event, min_shift, max_shift);
-        self.events.push(event);
-        self.events.last().unwrap()
+        self.register_net_event(event);
     }
 }
 
@@ -847,7 +1064,6 @@ impl RpcRegistry for EventManagerState {
                 .borrow_mut()
                 .add_entry(LogEntry::RpcMessageSent(entry));
         }
-        let (min_net_delay, max_net_delay) = self.system().network().delays_range();
         let from_proc = self.system().proc_by_addr(&request.from).unwrap();
         let has_listener = self.rpc.borrow().has_listener(&request.to);
         let (waiter, trigger) = trigger::make_trigger();
@@ -881,8 +1097,7 @@ impl RpcRegistry for EventManagerState {
             }
         };
 
-        self.register_event(&event, min_net_delay, max_net_delay);
-        self.events.push(event);
+        self.register_net_event(event);
 
         let rpc = self.rpc.clone();
         let (sender, receiver) = oneshot::channel();
@@ -1019,8 +1234,6 @@ impl RpcRegistry for EventManagerState {
             EventInfo::RpcEvent(e)
         };
 
-        let (min_net_delay, max_net_delay) = self.system().network().delays_range();
-
         let (waiter, trigger) = make_trigger();
 
         let event = Event {
@@ -1030,8 +1243,7 @@ impl RpcRegistry for EventManagerState {
             on_happen: Some(trigger),
         };
 
-        self.register_event(&event, min_net_delay, max_net_delay);
-        self.events.push(event);
+        self.register_net_event(event);
 
         let rpc = self.rpc.clone();
         let log = self.event_log.clone();
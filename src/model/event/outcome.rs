@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use crate::{model::fs::event::FsEventOutcome, model::tcp::TcpError, RpcResult};
+use crate::{model::fs::event::FsEventOutcome, model::tcp::TcpError, FsError, RpcResult};
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -13,6 +13,7 @@ pub enum EventOutcomeKind {
     RpcMessageDelivered,
     RpcEventHappen(RpcResult<()>),
     FsEventHappen(FsEventOutcome),
+    FsEventFailed(FsError),
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -3,4 +3,7 @@ pub struct EventStat {
     pub udp_msg_dropped: usize,
     pub nodes_crashed: usize,
     pub nodes_shutdown: usize,
+    pub nodes_restarted: usize,
+    pub network_partitions: usize,
+    pub disk_faults: usize,
 }
@@ -1,6 +1,12 @@
 use std::time::Duration;
 
-use crate::model::event::{driver::EventDriver, Event};
+use crate::{
+    model::{
+        event::{driver::EventDriver, Event},
+        HashType,
+    },
+    Address,
+};
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -25,7 +31,15 @@ impl EventDriver for TestEventDriver {
         self.events.remove(index);
     }
 
-    fn hash_pending(&self) -> u64 {
+    fn hash_pending(&self) -> HashType {
+        0
+    }
+
+    fn choose(&mut self, _n: usize) -> usize {
+        0
+    }
+
+    fn random_seed(&mut self, _proc: &Address) -> u64 {
         0
     }
 }
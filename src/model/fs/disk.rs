@@ -84,6 +84,11 @@ impl Disk {
         kind: FsEventKind,
         outcome: FsEventOutcome,
     ) {
+        // failed write does not occupy disk space
+        if let (FsEventKind::Write { len, .. }, Err(FsError::IoFailure)) = (&kind, &outcome) {
+            self.used = self.used.saturating_sub(*len);
+        }
+
         let event = FsEvent {
             initiated_by: Address::new(self.node.clone(), proc),
             kind,
@@ -180,8 +180,7 @@ impl FsManagerHandle {
         file: &File,
         kind: FsEventKind,
     ) -> Result<Waiter, FsError> {
-        let available = self.state().borrow().available;
-        if available {
+        if self.available() {
             Ok(self
                 .state()
                 .borrow_mut()
@@ -193,10 +192,13 @@ impl FsManagerHandle {
     }
 
     pub fn register_event_happen(&self, file: &File, kind: FsEventKind, outcome: FsEventOutcome) {
-        self.state()
-            .borrow_mut()
-            .disk
-            .on_request_completed(file.owner_proc.clone(), kind, outcome);
+        // file system could crash while request was pending
+        if let Some(state) = self.0.upgrade() {
+            state
+                .borrow_mut()
+                .disk
+                .on_request_completed(file.owner_proc.clone(), kind, outcome);
+        }
     }
 
     pub fn crash(&self) {
@@ -221,7 +223,10 @@ impl FsManagerHandle {
     }
 
     pub fn available(&self) -> bool {
-        self.state().borrow().available
+        self.0
+            .upgrade()
+            .map(|state| state.borrow().available)
+            .unwrap_or(false)
     }
 }
 
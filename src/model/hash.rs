@@ -1,8 +1,12 @@
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::hash::Hash;
 
-use crate::model::{
-    event::{info::EventInfo, Event},
-    node::Node,
+use crate::{
+    model::{
+        event::{info::EventInfo, Event},
+        node::Node,
+        HashType,
+    },
+    util::hash::Hasher128,
 };
 
 pub use crate::{util, Address};
@@ -24,28 +28,38 @@ impl<'a> HashContext<'a> {
         self.node_register.role(node).unwrap_or(node)
     }
 
-    fn hash_address(&self, a: &Address) -> u64 {
-        let mut hasher = DefaultHasher::new();
+    fn hash_address(&self, a: &Address) -> HashType {
+        let mut hasher = Hasher128::new();
         self.node_repr(&a.node).hash(&mut hasher);
         a.process.hash(&mut hasher);
-        hasher.finish()
+        hasher.finish128()
     }
 
-    fn hash_node(&self, node: &Node) -> u64 {
-        let mut hasher = DefaultHasher::new();
+    fn hash_node(&self, node: &Node) -> HashType {
+        let mut hasher = Hasher128::new();
         node.hash(&mut hasher);
         self.node_repr(&node.name).hash(&mut hasher);
-        hasher.finish()
+        hasher.finish128()
     }
 
-    pub fn hash_nodes(&self, nodes: impl Iterator<Item = &'a Node>) -> u64 {
+    pub fn hash_nodes(&self, nodes: impl Iterator<Item = &'a Node>) -> HashType {
         util::hash::hash_multiset(nodes.map(|n| self.hash_node(n)))
     }
 
+    pub fn hash_partition(&self, groups: &'a [Vec<String>]) -> HashType {
+        util::hash::hash_multiset(groups.iter().map(|group| {
+            util::hash::hash_multiset(group.iter().map(|node| {
+                let mut hasher = Hasher128::new();
+                self.node_repr(node).hash(&mut hasher);
+                hasher.finish128()
+            }))
+        }))
+    }
+
     ////////////////////////////////////////////////////////////////////////////////
 
-    fn hash_event(&self, event: &'a Event) -> u64 {
-        let mut hasher = DefaultHasher::new();
+    fn hash_event(&self, event: &'a Event) -> HashType {
+        let mut hasher = Hasher128::new();
         match &event.info {
             EventInfo::UdpMessage(udp) => {
                 udp.content.hash(&mut hasher);
@@ -80,10 +94,10 @@ impl<'a> HashContext<'a> {
                 self.hash_address(&e.to.address()).hash(&mut hasher);
             }
         }
-        hasher.finish()
+        hasher.finish128()
     }
 
-    pub fn hash_events(&self, events: impl Iterator<Item = &'a Event> + Clone) -> u64 {
+    pub fn hash_events(&self, events: impl Iterator<Item = &'a Event> + Clone) -> HashType {
         util::hash::hash_multiset(events.map(|e| self.hash_event(e)))
     }
 }
@@ -658,6 +658,31 @@ impl Display for ProcessInfo {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Debug, Clone)]
+pub struct ProcessChose {
+    pub process: Address,
+    pub time: Duration,
+    pub value: usize,
+    pub options: usize,
+}
+
+impl Display for ProcessChose {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            format!(
+                "{:5?} {:>12} ???? chose {} of {}",
+                self.time,
+                self.process.to_string(),
+                self.value,
+                self.options
+            )
+            .purple()
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NodeCrashed {
     pub node: String,
@@ -672,6 +697,65 @@ impl Display for NodeCrashed {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Debug, Clone)]
+pub struct NetworkPartitioned {
+    pub groups: Vec<Vec<String>>,
+    pub time: Duration,
+}
+
+impl Display for NetworkPartitioned {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:5?} {:>12}  SPLIT", self.time, "network")?;
+        for group in self.groups.iter() {
+            write!(f, " [{}]", group.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub struct NetworkHealed {
+    pub time: Duration,
+}
+
+impl Display for NetworkHealed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:5?} {:>12}  HEAL", self.time, "network")
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub struct NodeRestarted {
+    pub node: String,
+    pub time: Duration,
+}
+
+impl Display for NodeRestarted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:5?} {:>12}  RS", self.time, self.node)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub struct FsCrashed {
+    pub node: String,
+    pub time: Duration,
+}
+
+impl Display for FsCrashed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:5?} {:>12}  FS 💥", self.time, self.node)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Debug, Clone)]
 pub struct NodeShutdown {
     pub node: String,
@@ -700,8 +784,13 @@ pub enum LogEntry {
     FutureFellAsleep(FutureFellAsleep),
     FutureWokeUp(FutureWokeUp),
     ProcessInfo(ProcessInfo),
+    ProcessChose(ProcessChose),
     NodeCrashed(NodeCrashed),
     NodeShutdown(NodeShutdown),
+    NodeRestarted(NodeRestarted),
+    NetworkPartitioned(NetworkPartitioned),
+    NetworkHealed(NetworkHealed),
+    FsCrashed(FsCrashed),
     CreateFileRequested(CreateFileRequested),
     DeleteFileRequested(DeleteFileRequested),
     ReadFileInitiated(ReadFileInitiated),
@@ -731,6 +820,7 @@ impl Display for LogEntry {
             LogEntry::FutureFellAsleep(e) => write!(f, "{}", e),
             LogEntry::FutureWokeUp(e) => write!(f, "{}", e),
             LogEntry::ProcessInfo(e) => write!(f, "{}", e),
+            LogEntry::ProcessChose(e) => write!(f, "{}", e),
             LogEntry::NodeCrashed(e) => write!(f, "{}", e),
             LogEntry::CreateFileRequested(e) => write!(f, "{}", e),
             LogEntry::DeleteFileRequested(e) => write!(f, "{}", e),
@@ -746,6 +836,10 @@ impl Display for LogEntry {
             LogEntry::TimerSet(e) => write!(f, "{}", e),
             LogEntry::TimerCancelled(e) => write!(f, "{}", e),
             LogEntry::NodeShutdown(e) => write!(f, "{}", e),
+            LogEntry::NodeRestarted(e) => write!(f, "{}", e),
+            LogEntry::NetworkPartitioned(e) => write!(f, "{}", e),
+            LogEntry::NetworkHealed(e) => write!(f, "{}", e),
+            LogEntry::FsCrashed(e) => write!(f, "{}", e),
         }
     }
 }
@@ -26,7 +26,7 @@ pub use log::{Log, LogEntry};
 pub(crate) use runtime::JoinHandle;
 pub(crate) use timer::Timer;
 
-pub use net::send_message;
+pub use net::{send_message, PartitionPolicy};
 
 pub use node::Node;
 
@@ -1,5 +1,6 @@
 use std::{
     cell::RefCell,
+    collections::BTreeMap,
     rc::{Rc, Weak},
     time::Duration,
 };
@@ -9,10 +10,27 @@ use crate::Address;
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Describes what happens with TCP packets and RPC messages
+/// sent between nodes separated by the network partition.
+/// UDP messages between separated nodes are always dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PartitionPolicy {
+    /// Sender gets the connection refused error.
+    #[default]
+    Refuse,
+    /// Messages are held until the nodes are connected again.
+    Hang,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Clone)]
 pub struct NetworkState {
     pub min_packet_delay: Duration,
     pub max_packet_delay: Duration,
+    // node -> group, not listed nodes are in the group 0
+    pub groups: BTreeMap<String, usize>,
+    pub policy: PartitionPolicy,
 }
 
 impl NetworkState {
@@ -20,8 +38,14 @@ impl NetworkState {
         Self {
             min_packet_delay: cfg.min_packet_delay,
             max_packet_delay: cfg.max_packet_delay,
+            groups: Default::default(),
+            policy: Default::default(),
         }
     }
+
+    fn group(&self, node: &str) -> usize {
+        self.groups.get(node).copied().unwrap_or(0)
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -64,6 +88,53 @@ impl NetworkHandle {
         let state = state.borrow();
         (state.min_packet_delay, state.max_packet_delay)
     }
+
+    pub fn split(&self, groups: Vec<Vec<String>>) {
+        let state = self.state();
+        let mut state = state.borrow_mut();
+        state.groups = groups
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, group)| group.into_iter().map(move |node| (node, i + 1)))
+            .collect();
+    }
+
+    pub fn heal(&self) {
+        self.state().borrow_mut().groups.clear();
+    }
+
+    pub fn partitioned(&self) -> bool {
+        !self.state().borrow().groups.is_empty()
+    }
+
+    pub fn connected(&self, from: &str, to: &str) -> bool {
+        let state = self.state();
+        let state = state.borrow();
+        state.group(from) == state.group(to)
+    }
+
+    /// Returns groups of the explicitly partitioned nodes.
+    pub fn groups(&self) -> Vec<Vec<String>> {
+        let state = self.state();
+        let state = state.borrow();
+        let mut groups = Vec::<Vec<String>>::new();
+        for (node, group) in state.groups.iter() {
+            if groups.len() < *group {
+                groups.resize(*group, Vec::new());
+            }
+            groups[group - 1].push(node.clone());
+        }
+        groups.retain(|g| !g.is_empty());
+        groups
+    }
+
+    pub fn set_partition_policy(&self, policy: PartitionPolicy) {
+        self.state().borrow_mut().policy = policy;
+    }
+
+    pub fn partition_policy(&self) -> PartitionPolicy {
+        self.state().borrow().policy
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -53,8 +53,8 @@ impl Node {
         }
     }
 
-    pub(crate) fn crash_fs(&mut self) {
-        let _ = self.fs.take();
+    pub(crate) fn crash_fs(&mut self) -> Option<FsManager> {
+        self.fs.take()
     }
 
     pub(crate) fn shutdown_fs(&mut self) -> Result<(), Error> {
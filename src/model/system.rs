@@ -1,7 +1,7 @@
 use std::{
     any::Any,
     cell::RefCell,
-    collections::{btree_map::Entry, BTreeMap},
+    collections::{btree_map::Entry, BTreeMap, BTreeSet, HashMap},
     rc::{Rc, Weak},
     time::Duration,
 };
@@ -12,6 +12,7 @@ use crate::model::{
     net::Config as NetConfig,
     runtime::Runtime,
 };
+use crate::util;
 
 pub use crate::{Address, Process};
 
@@ -20,7 +21,7 @@ use super::{
     error::Error,
     hash::HashContext,
     log::Log,
-    net::{Network, NetworkHandle},
+    net::{Network, NetworkHandle, PartitionPolicy},
     node::{Node, NodeRoleRegister},
     proc::ProcessHandle,
 };
@@ -28,13 +29,18 @@ use super::{
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Type of hash of the system model state.
-pub type HashType = u64;
+pub type HashType = u128;
+
+////////////////////////////////////////////////////////////////////////////////
+
+type ProcessFactory = Rc<dyn Fn(SystemHandle, &str)>;
 
 ////////////////////////////////////////////////////////////////////////////////
 
 struct SystemState {
     nodes: BTreeMap<String, Node>,
     roles: NodeRoleRegister,
+    factories: HashMap<String, ProcessFactory>,
     net: Network,
     rt: Runtime,
     event_manager: EventManager,
@@ -48,6 +54,7 @@ impl SystemState {
         let sys_state = SystemState {
             nodes: Default::default(),
             roles: Default::default(),
+            factories: Default::default(),
             net,
             rt,
             event_manager,
@@ -61,9 +68,14 @@ impl SystemState {
     fn hash(&self) -> HashType {
         let ctx = HashContext::new(&self.roles);
         let nodes_hash = ctx.hash_nodes(self.nodes.values());
+        let groups = self.net.handle().groups();
+        let partition_hash = if groups.is_empty() {
+            0
+        } else {
+            ctx.hash_partition(&groups)
+        };
         let events_hash = self.event_manager.hash(ctx);
-        nodes_hash ^ events_hash
-        // util::hash::hash_list([nodes_hash, events_hash].into_iter())
+        util::hash::hash_list([nodes_hash, events_hash, partition_hash].into_iter())
     }
 }
 
@@ -159,6 +171,128 @@ impl SystemHandle {
         self.state().borrow().net.handle()
     }
 
+    /// Split the network into the provided groups of nodes.
+    /// Nodes which are not listed in any group form one more group.
+    /// Nodes from different groups can not communicate
+    /// until the network is healed. Previous partition is replaced.
+    /// If the groups do not separate any nodes, the call does nothing.
+    pub fn partition<G, N>(&self, groups: impl IntoIterator<Item = G>) -> Result<(), Error>
+    where
+        G: IntoIterator<Item = N>,
+        N: Into<String>,
+    {
+        let groups = groups
+            .into_iter()
+            .map(|g| g.into_iter().map(Into::into).collect::<Vec<String>>())
+            .collect::<Vec<_>>();
+        {
+            let state = self.state();
+            let state = state.borrow();
+            let mut listed = BTreeSet::new();
+            for node in groups.iter().flatten() {
+                if !state.nodes.contains_key(node) {
+                    return Err(Error::NotFound);
+                }
+                if !listed.insert(node) {
+                    return Err(Error::AlreadyExists);
+                }
+            }
+            let unlisted = (listed.len() < state.nodes.len()) as usize;
+            if groups.iter().filter(|g| !g.is_empty()).count() + unlisted < 2 {
+                return Ok(());
+            }
+        }
+
+        let net = self.network();
+        net.split(groups);
+        self.state()
+            .borrow()
+            .event_manager
+            .handle()
+            .on_network_partition(net.groups());
+
+        Ok(())
+    }
+
+    /// Isolate node from all other nodes.
+    /// Previous partition is replaced.
+    pub fn isolate(&self, node: impl Into<String>) -> Result<(), Error> {
+        self.partition([[node.into()]])
+    }
+
+    /// Heal the network partition.
+    pub fn heal(&self) {
+        let net = self.network();
+        if !net.partitioned() {
+            return;
+        }
+        net.heal();
+        self.state()
+            .borrow()
+            .event_manager
+            .handle()
+            .on_network_heal();
+    }
+
+    /// Returns all splits of the nodes into two groups
+    /// as the indices of the nodes of one of the groups.
+    /// Splits which differ only by the nodes with the same role are skipped.
+    ///
+    /// Nodes with the same role are interchangeable, so the split is defined
+    /// by the number of nodes of every role in the group, and only such numbers are enumerated.
+    pub(crate) fn partitions_index(&self) -> Vec<Vec<usize>> {
+        let state = self.state();
+        let state = state.borrow();
+        let mut roles = BTreeMap::<&str, Vec<usize>>::new();
+        for (i, node) in state.nodes.keys().enumerate() {
+            let role = state.roles.role(node).unwrap_or(node);
+            roles.entry(role).or_default().push(i);
+        }
+        let roles = roles.into_values().collect::<Vec<_>>();
+
+        let mut result = Vec::new();
+        let mut counts = vec![0; roles.len()];
+        // next numbers of the nodes of every role in the group
+        while let Some(role) = (0..roles.len()).find(|r| counts[*r] < roles[*r].len()) {
+            counts[role] += 1;
+            counts[..role].fill(0);
+
+            // the other group has the same split, so it is enumerated once
+            let other = roles
+                .iter()
+                .zip(counts.iter())
+                .map(|(nodes, count)| nodes.len() - count);
+            let whole = counts.iter().zip(roles.iter()).all(|(c, n)| *c == n.len());
+            if whole || counts.iter().copied().gt(other) {
+                continue;
+            }
+            let mut group = roles
+                .iter()
+                .zip(counts.iter())
+                .flat_map(|(nodes, count)| nodes[..*count].iter().copied())
+                .collect::<Vec<_>>();
+            group.sort();
+            result.push(group);
+        }
+        result
+    }
+
+    pub(crate) fn partition_index(&self, nodes: &[usize]) {
+        let group = {
+            let state = self.state();
+            let state = state.borrow();
+            let names = state.nodes.keys().collect::<Vec<_>>();
+            nodes.iter().map(|i| names[*i].clone()).collect::<Vec<_>>()
+        };
+        self.partition([group]).unwrap();
+    }
+
+    /// Set what happens with TCP packets and RPC messages
+    /// sent between the separated nodes.
+    pub fn set_partition_policy(&self, policy: PartitionPolicy) {
+        self.network().set_partition_policy(policy);
+    }
+
     ////////////////////////////////////////////////////////////////////////////////
 
     /// Send local message to the process.
@@ -248,17 +382,43 @@ impl SystemHandle {
     }
 
     /// Crash node file system.
+    /// Pending file system requests of the node fail.
     pub fn crash_fs(&self, node: impl Into<String>) -> Result<(), Error> {
         let node = node.into();
-        self.state()
+        let fs = self
+            .state()
             .borrow_mut()
             .nodes
             .get_mut(&node)
             .ok_or(Error::NotFound)?
             .crash_fs();
+
+        if let Some(fs) = fs {
+            fs.handle().crash();
+
+            self.state()
+                .borrow()
+                .event_manager
+                .handle()
+                .on_fs_crash(node.as_str());
+
+            drop(fs);
+            self.run_async_tasks();
+        }
+
         Ok(())
     }
 
+    pub(crate) fn fs_available_index(&self, i: usize) -> bool {
+        let key = self.state().borrow().nodes.keys().nth(i).cloned().unwrap();
+        self.fs(&key).map(|fs| fs.available()).unwrap_or(false)
+    }
+
+    pub(crate) fn crash_fs_index(&self, i: usize) {
+        let key = self.state().borrow().nodes.keys().nth(i).cloned().unwrap();
+        self.crash_fs(key).unwrap();
+    }
+
     /// Shutdown node filesy stem.
     pub fn shutdown_fs(&self, node: impl Into<String>) -> Result<(), Error> {
         let node = node.into();
@@ -310,6 +470,10 @@ impl SystemHandle {
             .pending_events()
     }
 
+    pub(crate) fn events_count(&self) -> usize {
+        self.state().borrow().event_manager.handle().events_count()
+    }
+
     ////////////////////////////////////////////////////////////////////////////////
 
     pub(crate) fn nodes_count(&self) -> usize {
@@ -419,16 +583,68 @@ impl SystemHandle {
     /// Allows to restart shutdown node.
     pub fn restart_node(&self, node: impl Into<String>) -> Result<(), Error> {
         let node = node.into();
-        let state = self.state();
-        let mut state = state.borrow_mut();
-        let node = state.nodes.get_mut(&node).ok_or(Error::NotFound)?;
-        node.shutdown = false;
-        if let Some(fs) = node.fs.as_ref() {
-            fs.handle().raise();
+        {
+            let state = self.state();
+            let mut state = state.borrow_mut();
+            let node = state.nodes.get_mut(&node).ok_or(Error::NotFound)?;
+            node.shutdown = false;
+            if let Some(fs) = node.fs.as_ref() {
+                fs.handle().raise();
+            }
         }
+
+        self.state()
+            .borrow()
+            .event_manager
+            .handle()
+            .on_node_restart(node.as_str());
+
         Ok(())
     }
 
+    /// Register process factory for the nodes with specified role.
+    /// Factory is called by the model checker after the node restart
+    /// and should add processes on the node and send them init messages.
+    pub fn register_process_factory(
+        &self,
+        role: impl Into<String>,
+        factory: impl Fn(SystemHandle, &str) + 'static,
+    ) {
+        self.state()
+            .borrow_mut()
+            .factories
+            .insert(role.into(), Rc::new(factory));
+    }
+
+    pub(crate) fn node_restartable_index(&self, i: usize) -> bool {
+        let state = self.state();
+        let state = state.borrow();
+        let (name, node) = state.nodes.iter().nth(i).unwrap();
+        node.shutdown
+            && state
+                .roles
+                .role(name)
+                .map(|role| state.factories.contains_key(role))
+                .unwrap_or(false)
+    }
+
+    pub(crate) fn restart_node_index(&self, i: usize) {
+        let (node, factory) = {
+            let state = self.state();
+            let state = state.borrow();
+            let node = state.nodes.keys().nth(i).cloned().unwrap();
+            let factory = state
+                .roles
+                .role(&node)
+                .and_then(|role| state.factories.get(role))
+                .cloned()
+                .unwrap();
+            (node, factory)
+        };
+        self.restart_node(node.as_str()).unwrap();
+        factory(self.clone(), node.as_str());
+    }
+
     ////////////////////////////////////////////////////////////////////////////////
 
     /// Allows to add process on node.
@@ -11,3 +11,6 @@ mod rpc_fails;
 
 #[cfg(test)]
 mod node;
+
+#[cfg(test)]
+mod partition;
@@ -0,0 +1,275 @@
+use std::time::Duration;
+
+use crate::{
+    detsim::{Simulation, StepConfig},
+    model::{
+        log::LogEntry, net::send_message, node::Node, HashType, PartitionPolicy, TcpError,
+        TcpListener, TcpStream,
+    },
+    rpc, send_local, spawn, Address, Process, RpcListener,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+
+struct Sender {
+    other: Address,
+}
+
+impl Process for Sender {
+    fn on_message(&mut self, _from: Address, content: String) {
+        send_local(content);
+    }
+
+    fn on_local_message(&mut self, content: String) {
+        if content == "rpc" {
+            let to = self.other.clone();
+            spawn(async move {
+                match rpc(to, 0, &"ping".to_string()).await {
+                    Ok(_) => send_local("ok"),
+                    Err(err) => send_local(err.to_string()),
+                }
+            });
+        } else if content == "tcp" {
+            let to = self.other.clone();
+            spawn(async move {
+                let result = async {
+                    let mut stream = TcpStream::connect(&to).await?;
+                    stream.send(b"ping").await?;
+                    let mut buf = [0u8; 4];
+                    let read = stream.recv(&mut buf).await?;
+                    Ok::<_, TcpError>(String::from_utf8_lossy(&buf[..read]).to_string())
+                };
+                match result.await {
+                    Ok(reply) => send_local(reply),
+                    Err(err) => send_local(err.to_string()),
+                }
+            });
+        } else {
+            send_message(&self.other, content);
+        }
+    }
+
+    fn hash(&self) -> HashType {
+        0
+    }
+}
+
+struct Echo {}
+
+impl Process for Echo {
+    fn on_message(&mut self, from: Address, content: String) {
+        send_message(&from, content);
+    }
+
+    fn on_local_message(&mut self, content: String) {
+        assert_eq!(content, "listen");
+        let mut listener = RpcListener::register().unwrap();
+        spawn(async move {
+            loop {
+                let request = listener.listen().await;
+                request.reply(&"pong".to_string()).unwrap();
+            }
+        });
+        spawn(async move {
+            let mut stream = TcpListener::listen().await.unwrap();
+            let mut buf = [0u8; 4];
+            stream.recv(&mut buf).await.unwrap();
+            stream.send(b"pong").await.unwrap();
+        });
+    }
+
+    fn hash(&self) -> HashType {
+        0
+    }
+}
+
+fn build_sim() -> Simulation {
+    let sim = Simulation::new(123);
+    let system = sim.system();
+    system
+        .network()
+        .set_delays(Duration::from_millis(100), Duration::from_millis(200))
+        .unwrap();
+
+    let mut node = Node::new("n1");
+    node.add_proc(
+        "sender",
+        Sender {
+            other: Address::new("n2", "echo"),
+        },
+    )
+    .unwrap();
+    system.add_node(node).unwrap();
+
+    let mut node = Node::new("n2");
+    node.add_proc("echo", Echo {}).unwrap();
+    system.add_node(node).unwrap();
+
+    system.add_node(Node::new("n3")).unwrap();
+
+    system
+        .send_local(&Address::new("n2", "echo"), "listen")
+        .unwrap();
+
+    sim
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn udp_dropped() {
+    let sim = build_sim();
+    let system = sim.system();
+    let sender = Address::new("n1", "sender");
+
+    system.isolate("n2").unwrap();
+    system.send_local(&sender, "hello").unwrap();
+    sim.step_until_no_events(&StepConfig::no_drops());
+    assert!(system.read_locals("n1", "sender").unwrap().is_empty());
+
+    system.heal();
+    system.send_local(&sender, "hello").unwrap();
+    sim.step_until_no_events(&StepConfig::no_drops());
+    assert_eq!(system.read_locals("n1", "sender").unwrap(), vec!["hello"]);
+
+    let log = system.log();
+    assert!(log
+        .iter()
+        .any(|e| matches!(e, LogEntry::NetworkPartitioned(..))));
+    assert!(log.iter().any(|e| matches!(e, LogEntry::NetworkHealed(..))));
+    println!("{}", log);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn in_flight_udp_dropped() {
+    let sim = build_sim();
+    let system = sim.system();
+
+    system
+        .send_local(&Address::new("n1", "sender"), "hello")
+        .unwrap();
+    system.partition([["n1", "n3"]]).unwrap();
+    sim.step_until_no_events(&StepConfig::no_drops());
+    assert!(system.read_locals("n1", "sender").unwrap().is_empty());
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn same_group_connected() {
+    let sim = build_sim();
+    let system = sim.system();
+
+    system.partition([vec!["n1", "n2"], vec!["n3"]]).unwrap();
+    system
+        .send_local(&Address::new("n1", "sender"), "hello")
+        .unwrap();
+    sim.step_until_no_events(&StepConfig::no_drops());
+    assert_eq!(system.read_locals("n1", "sender").unwrap(), vec!["hello"]);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn rpc_refused() {
+    let sim = build_sim();
+    let system = sim.system();
+
+    system.isolate("n1").unwrap();
+    system
+        .send_local(&Address::new("n1", "sender"), "rpc")
+        .unwrap();
+    sim.step_until_no_events(&StepConfig::no_drops());
+    assert_eq!(
+        system.read_locals("n1", "sender").unwrap(),
+        vec!["connection refused"]
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn rpc_hang() {
+    let sim = build_sim();
+    let system = sim.system();
+    system.set_partition_policy(PartitionPolicy::Hang);
+
+    system.isolate("n1").unwrap();
+    system
+        .send_local(&Address::new("n1", "sender"), "rpc")
+        .unwrap();
+    sim.step_until_no_events(&StepConfig::no_drops());
+    assert!(system.read_locals("n1", "sender").unwrap().is_empty());
+
+    system.heal();
+    sim.step_until_no_events(&StepConfig::no_drops());
+    assert_eq!(system.read_locals("n1", "sender").unwrap(), vec!["ok"]);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn tcp_refused() {
+    let sim = build_sim();
+    let system = sim.system();
+
+    system.isolate("n2").unwrap();
+    system
+        .send_local(&Address::new("n1", "sender"), "tcp")
+        .unwrap();
+    sim.step_until_no_events(&StepConfig::no_drops());
+    assert_eq!(
+        system.read_locals("n1", "sender").unwrap(),
+        vec!["connection refused"]
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn tcp_hang() {
+    let sim = build_sim();
+    let system = sim.system();
+    system.set_partition_policy(PartitionPolicy::Hang);
+
+    system.isolate("n2").unwrap();
+    system
+        .send_local(&Address::new("n1", "sender"), "tcp")
+        .unwrap();
+    sim.step_until_no_events(&StepConfig::no_drops());
+    assert!(system.read_locals("n1", "sender").unwrap().is_empty());
+
+    system.heal();
+    sim.step_until_no_events(&StepConfig::no_drops());
+    assert_eq!(system.read_locals("n1", "sender").unwrap(), vec!["pong"]);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn partition_without_separated_nodes() {
+    let sim = build_sim();
+    let system = sim.system();
+
+    system.partition(Vec::<Vec<String>>::new()).unwrap();
+    system.partition([Vec::<String>::new()]).unwrap();
+    system.partition([vec!["n1", "n2", "n3"]]).unwrap();
+    assert!(!system.network().partitioned());
+    assert!(!system
+        .log()
+        .iter()
+        .any(|e| matches!(e, LogEntry::NetworkPartitioned(..))));
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn partition_unknown_node() {
+    let sim = build_sim();
+    let system = sim.system();
+    assert!(system.isolate("n4").is_err());
+    assert!(system.partition([vec!["n1"], vec!["n1"]]).is_err());
+    assert!(!system.network().partitioned());
+}
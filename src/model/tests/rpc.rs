@@ -47,7 +47,7 @@ impl Process for RpcClient {
     }
 
     fn hash(&self) -> HashType {
-        self.cur_value
+        self.cur_value as HashType
     }
 }
 
@@ -82,7 +82,7 @@ impl Process for IncServer {
     }
 
     fn hash(&self) -> HashType {
-        *self.value.borrow()
+        *self.value.borrow() as HashType
     }
 }
 
@@ -10,7 +10,7 @@ use crate::{
     },
     model::net::Config as NetConfig,
     model::node::Node,
-    model::system::System,
+    model::system::{HashType, System},
     Address,
 };
 
@@ -35,7 +35,15 @@ impl EventDriver for EventCollector {
         unreachable!()
     }
 
-    fn hash_pending(&self) -> u64 {
+    fn hash_pending(&self) -> HashType {
+        0
+    }
+
+    fn choose(&mut self, _n: usize) -> usize {
+        0
+    }
+
+    fn random_seed(&mut self, _proc: &Address) -> u64 {
         0
     }
 }
@@ -75,6 +75,14 @@ impl Context {
         self.proc.node.set_random_timer(min_duration, max_duration)
     }
 
+    pub fn choose(&self, n: usize) -> usize {
+        self.proc.node.choose(n)
+    }
+
+    pub fn random_seed(&self) -> u64 {
+        self.proc.node.random_seed()
+    }
+
     pub fn register_rpc_listener(&self) -> RpcResult<RpcListener> {
         self.proc.node.register_rpc_listener(self.proc.name())
     }
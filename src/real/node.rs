@@ -94,6 +94,19 @@ impl RealNodeState {
         self.set_timer(duration)
     }
 
+    ////////////////////////////////////////////////////////////////////////////////
+    // Choice
+    ////////////////////////////////////////////////////////////////////////////////
+
+    fn choose(&self, n: usize) -> usize {
+        assert!(n > 0, "can not choose from zero options");
+        self.rng.borrow_mut().random_range(0..n)
+    }
+
+    fn random_seed(&self) -> u64 {
+        self.rng.borrow_mut().random()
+    }
+
     ////////////////////////////////////////////////////////////////////////////////
     // RPC
     ////////////////////////////////////////////////////////////////////////////////
@@ -145,6 +158,14 @@ impl RealNodeHandle {
             .set_random_timer(min_duration, max_duration)
     }
 
+    pub fn choose(&self, n: usize) -> usize {
+        self.0.upgrade().unwrap().borrow().choose(n)
+    }
+
+    pub fn random_seed(&self) -> u64 {
+        self.0.upgrade().unwrap().borrow().random_seed()
+    }
+
     ////////////////////////////////////////////////////////////////////////////////
 
     pub fn spawn<F>(&self, f: F) -> JoinHandle<F::Output>
@@ -294,4 +315,17 @@ mod tests {
         let duration = start.elapsed();
         assert!(duration >= Duration::from_millis(5));
     }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn random_seed() {
+        let seeds = |seed| {
+            let state = RealNodeState::new("node", seed, Default::default(), String::default());
+            (0..3).map(|_| state.random_seed()).collect::<Vec<_>>()
+        };
+        assert_eq!(seeds(123), seeds(123));
+        assert_ne!(seeds(123), seeds(321));
+        assert_ne!(seeds(123)[0], seeds(123)[1]);
+    }
 }
@@ -4,8 +4,63 @@ use std::hash::{DefaultHasher, Hash, Hasher};
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Hasher, which produces 128-bit fingerprints,
+/// so the collisions of the different states are unlikely
+/// even on the searches with the billions of states.
+///
+/// Fingerprint is made by two independent 64-bit hashers.
+#[derive(Clone, Debug)]
+pub struct Hasher128 {
+    low: DefaultHasher,
+    high: DefaultHasher,
+}
+
+impl Default for Hasher128 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher128 {
+    /// Make new hasher.
+    pub fn new() -> Self {
+        let mut high = DefaultHasher::new();
+        // makes the high half independent from the low one
+        high.write_u64(0x9e37_79b9_7f4a_7c15);
+        Self {
+            low: DefaultHasher::new(),
+            high,
+        }
+    }
+
+    /// Returns 128-bit fingerprint of the written values.
+    pub fn finish128(&self) -> u128 {
+        ((self.high.finish() as u128) << 64) | self.low.finish() as u128
+    }
+}
+
+impl Hasher for Hasher128 {
+    fn finish(&self) -> u64 {
+        self.low.finish()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.low.write(bytes);
+        self.high.write(bytes);
+    }
+}
+
+/// Returns 128-bit fingerprint of the value.
+pub fn fingerprint<T: Hash + ?Sized>(value: &T) -> u128 {
+    let mut hasher = Hasher128::new();
+    value.hash(&mut hasher);
+    hasher.finish128()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// Allows to hash multiset.
-pub fn hash_multiset(elems: impl Iterator<Item = u64>) -> u64 {
+pub fn hash_multiset(elems: impl Iterator<Item = u128>) -> u128 {
     let mut v = elems.collect::<Vec<_>>();
     v.sort();
     hash_list(v.iter().copied())
@@ -14,17 +69,17 @@ pub fn hash_multiset(elems: impl Iterator<Item = u64>) -> u64 {
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Allows to hash list.
-pub fn hash_list(elems: impl Iterator<Item = u64>) -> u64 {
-    let mut hasher = DefaultHasher::new();
+pub fn hash_list(elems: impl Iterator<Item = u128>) -> u128 {
+    let mut hasher = Hasher128::new();
     elems.for_each(|e| e.hash(&mut hasher));
-    hasher.finish()
+    hasher.finish128()
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
-    use super::{hash_list, hash_multiset};
+    use super::{fingerprint, hash_list, hash_multiset};
 
     ////////////////////////////////////////////////////////////////////////////////
 
@@ -43,4 +98,14 @@ mod tests {
     fn list() {
         assert!(hash_list([1, 2, 3].into_iter()) != hash_list([1, 3, 2].into_iter()));
     }
+
+    ////////////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn fingerprint_halves() {
+        let f = fingerprint("state");
+        assert_eq!(f, fingerprint("state"));
+        assert_ne!(f, fingerprint("other state"));
+        assert_ne!(f >> 64, f & u64::MAX as u128);
+    }
 }
@@ -2,8 +2,9 @@
 
 pub(crate) mod append;
 pub mod cancel;
-pub(crate) mod hash;
+pub mod hash;
 pub mod oneshot;
+pub mod replay;
 pub(crate) mod send;
 pub(crate) mod trigger;
 pub(crate) mod unbounded;
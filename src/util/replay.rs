@@ -0,0 +1,43 @@
+//! Provides error of the trace replay.
+
+use std::fmt::{Debug, Display};
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Error which prevents the saved trace from being replayed
+/// (see [`crate::detsim::Simulation::replay`] and [`crate::mc::ModelChecker::replay`]).
+///
+/// Errors of the system reproduced by the trace are not replay errors
+/// and are returned as the result of the successful replay.
+pub enum ReplayError {
+    /// Trace file can not be read.
+    Io(std::io::Error),
+
+    /// Trace file has bad format.
+    Format(String),
+
+    /// Trace references apply function, which is not registered in the checker.
+    UnknownApply(String),
+
+    /// Simulation diverged from the trace, so the remaining steps were not followed.
+    Diverged(String),
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "Can not read trace: {}", err),
+            ReplayError::Format(err) => write!(f, "Bad trace format: {}", err),
+            ReplayError::UnknownApply(name) => {
+                write!(f, "Apply function '{}' is not registered", name)
+            }
+            ReplayError::Diverged(reason) => write!(f, "Replay diverged: {}", reason),
+        }
+    }
+}
+
+impl Debug for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}